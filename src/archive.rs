@@ -0,0 +1,216 @@
+// Copyright 2026 Recall Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Archive traversal for ingestion. Entries are read fully into memory, up to
+//! an optional size limit, and addressed as `<archive>!/<inner path>`.
+
+use std::fs::File;
+use std::io::Read;
+use std::path::Component;
+use std::path::Path;
+
+use anyhow::Context;
+use anyhow::Result;
+use flate2::read::GzDecoder;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveKind {
+    Zip,
+    Tar,
+    TarGz,
+}
+
+#[derive(Debug)]
+pub struct ArchiveEntry {
+    pub name: String,
+    /// Size from the entry header, or the bytes read when the entry turned out larger.
+    pub size: u64,
+    /// `None` when the entry is over the size limit and was not read.
+    pub bytes: Option<Vec<u8>>,
+}
+
+pub const ENTRY_SEPARATOR: &str = "!/";
+
+pub fn archive_kind(path: &Path) -> Option<ArchiveKind> {
+    let name = path.file_name()?.to_str()?.to_lowercase();
+    if name.ends_with(".zip") {
+        Some(ArchiveKind::Zip)
+    } else if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+        Some(ArchiveKind::TarGz)
+    } else if name.ends_with(".tar") {
+        Some(ArchiveKind::Tar)
+    } else {
+        None
+    }
+}
+
+pub fn entry_path(archive: &str, name: &str) -> String {
+    format!(
+        "{archive}{ENTRY_SEPARATOR}{}",
+        name.trim_start_matches("./")
+    )
+}
+
+/// Read the file entries of an archive. Entries whose header size is over `max_bytes` are not
+/// read at all, and reads stop one byte past the limit so a header that understates the size
+/// cannot exhaust memory either.
+pub fn read_entries(
+    path: &Path,
+    kind: ArchiveKind,
+    max_bytes: Option<u64>,
+) -> Result<Vec<ArchiveEntry>> {
+    let file = File::open(path).with_context(|| format!("open {}", path.display()))?;
+    match kind {
+        ArchiveKind::Zip => zip_entries(file, max_bytes),
+        ArchiveKind::Tar => tar_entries(file, max_bytes),
+        ArchiveKind::TarGz => tar_entries(GzDecoder::new(file), max_bytes),
+    }
+}
+
+fn read_entry(
+    name: String,
+    size: u64,
+    mut reader: impl Read,
+    max_bytes: Option<u64>,
+) -> Result<ArchiveEntry> {
+    let Some(limit) = max_bytes else {
+        let mut bytes = Vec::new();
+        reader
+            .read_to_end(&mut bytes)
+            .with_context(|| format!("read archive entry {name}"))?;
+        let size = bytes.len() as u64;
+        return Ok(ArchiveEntry {
+            name,
+            size,
+            bytes: Some(bytes),
+        });
+    };
+    if size > limit {
+        return Ok(ArchiveEntry {
+            name,
+            size,
+            bytes: None,
+        });
+    }
+    let mut bytes = Vec::with_capacity(size as usize);
+    reader
+        .take(limit + 1)
+        .read_to_end(&mut bytes)
+        .with_context(|| format!("read archive entry {name}"))?;
+    let size = bytes.len() as u64;
+    let bytes = (size <= limit).then_some(bytes);
+    Ok(ArchiveEntry { name, size, bytes })
+}
+
+fn zip_entries(file: File, max_bytes: Option<u64>) -> Result<Vec<ArchiveEntry>> {
+    let mut archive = zip::ZipArchive::new(file).context("read zip archive")?;
+    let mut entries = Vec::new();
+    for index in 0..archive.len() {
+        let mut entry = archive.by_index(index)?;
+        if !entry.is_file() {
+            continue;
+        }
+        // Skip entries that would escape the archive root (e.g. `../x`).
+        let Some(name) = entry.enclosed_name() else {
+            continue;
+        };
+        let name = name.to_string_lossy().replace('\\', "/");
+        let size = entry.size();
+        entries.push(read_entry(name, size, &mut entry, max_bytes)?);
+    }
+    Ok(entries)
+}
+
+fn tar_entries<R: Read>(reader: R, max_bytes: Option<u64>) -> Result<Vec<ArchiveEntry>> {
+    let mut archive = tar::Archive::new(reader);
+    let mut entries = Vec::new();
+    for entry in archive.entries().context("read tar archive")? {
+        let mut entry = entry?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let path = entry.path()?;
+        if path
+            .components()
+            .any(|c| !matches!(c, Component::Normal(_) | Component::CurDir))
+        {
+            continue;
+        }
+        let name = path.to_string_lossy().into_owned();
+        let size = entry.header().size()?;
+        entries.push(read_entry(name, size, &mut entry, max_bytes)?);
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+
+    #[test]
+    fn reads_tar_gz_entries() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("bundle.tar.gz");
+        let encoder =
+            flate2::write::GzEncoder::new(File::create(&path)?, flate2::Compression::default());
+        let mut builder = tar::Builder::new(encoder);
+        let body = b"# Guide\nhello";
+        let mut header = tar::Header::new_gnu();
+        header.set_size(body.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder.append_data(&mut header, "docs/a.md", &body[..])?;
+        builder.into_inner()?.finish()?.flush()?;
+
+        assert_eq!(archive_kind(&path), Some(ArchiveKind::TarGz));
+        let entries = read_entries(&path, ArchiveKind::TarGz, None)?;
+        assert_eq!(entries.len(), 1);
+        assert_eq!(
+            entry_path("bundle.tar.gz", &entries[0].name),
+            "bundle.tar.gz!/docs/a.md"
+        );
+        assert_eq!(entries[0].bytes.as_deref(), Some(&body[..]));
+
+        let entries = read_entries(&path, ArchiveKind::TarGz, Some(4))?;
+        assert_eq!(entries[0].size, body.len() as u64);
+        assert!(entries[0].bytes.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn zip_reads_stop_past_the_limit() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("bundle.zip");
+        let mut writer = zip::ZipWriter::new(File::create(&path)?);
+        let options = zip::write::SimpleFileOptions::default();
+        writer.start_file("small.txt", options)?;
+        writer.write_all(b"tiny")?;
+        writer.start_file("big.txt", options)?;
+        writer.write_all(&vec![b'x'; 4096])?;
+        writer.finish()?;
+
+        let entries = read_entries(&path, ArchiveKind::Zip, Some(16))?;
+        let sizes: Vec<_> = entries
+            .iter()
+            .map(|entry| (entry.name.as_str(), entry.size, entry.bytes.is_some()))
+            .collect();
+        assert_eq!(
+            sizes,
+            vec![("small.txt", 4, true), ("big.txt", 4096, false)]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,294 @@
+// Copyright 2026 Recall Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Chunking strategies and the per-glob `[[chunking]]` overrides from recall.toml.
+
+use anyhow::Context;
+use anyhow::Result;
+use globset::Glob;
+use globset::GlobMatcher;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::config::Config;
+use crate::tokenize::Tokenizer;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Strategy {
+    /// Split by parser: Markdown headings, code definitions, notebook cells.
+    Auto,
+    /// One block per document, cut into overlapping token windows.
+    Fixed,
+    /// Whole sentences packed up to `max_tokens`.
+    Sentence,
+    /// Blank-line separated paragraphs packed up to `max_tokens`.
+    Paragraph,
+    /// One block per Markdown heading section.
+    Heading,
+    /// Paragraphs, then lines, then sentences, then words until each piece fits.
+    Recursive,
+}
+
+impl Strategy {
+    pub fn parse(raw: &str) -> Result<Self> {
+        match raw.trim().to_lowercase().as_str() {
+            "auto" => Ok(Self::Auto),
+            "fixed" => Ok(Self::Fixed),
+            "sentence" => Ok(Self::Sentence),
+            "paragraph" => Ok(Self::Paragraph),
+            "heading" => Ok(Self::Heading),
+            "recursive" => Ok(Self::Recursive),
+            _ => anyhow::bail!(
+                "unsupported chunking strategy '{raw}'; supported: auto, fixed, sentence, paragraph, heading, recursive"
+            ),
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Auto => "auto",
+            Self::Fixed => "fixed",
+            Self::Sentence => "sentence",
+            Self::Paragraph => "paragraph",
+            Self::Heading => "heading",
+            Self::Recursive => "recursive",
+        }
+    }
+}
+
+/// One `[[chunking]]` table. Unset fields fall back to the global settings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChunkRule {
+    pub glob: String,
+    #[serde(default)]
+    pub strategy: Option<String>,
+    #[serde(default)]
+    pub max_tokens: Option<usize>,
+    #[serde(default)]
+    pub overlap_tokens: Option<usize>,
+}
+
+/// Chunking resolved for one document. Stored as JSON in `doc.chunking`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct ChunkSpec {
+    pub strategy: Strategy,
+    pub max_tokens: usize,
+    pub overlap_tokens: usize,
+}
+
+impl ChunkSpec {
+    fn new(strategy: Strategy, max_tokens: usize, overlap_tokens: usize) -> Self {
+        let max_tokens = max_tokens.max(1);
+        Self {
+            strategy,
+            max_tokens,
+            overlap_tokens: overlap_tokens.min(max_tokens - 1),
+        }
+    }
+
+    pub fn to_json(self) -> String {
+        serde_json::to_string(&self).unwrap_or_default()
+    }
+}
+
+/// Compiled `[[chunking]]` rules; the first rule whose glob matches a doc path wins.
+pub struct ChunkRules {
+    rules: Vec<(GlobMatcher, ChunkSpec)>,
+    default: ChunkSpec,
+}
+
+impl ChunkRules {
+    pub fn from_config(config: &Config) -> Result<Self> {
+        let default = ChunkSpec::new(Strategy::Auto, config.chunk_tokens, config.overlap_tokens);
+        let mut rules = Vec::new();
+        for rule in &config.chunking {
+            let matcher = Glob::new(&rule.glob)
+                .with_context(|| format!("invalid [[chunking]] glob '{}'", rule.glob))?
+                .compile_matcher();
+            let strategy = match &rule.strategy {
+                Some(raw) => Strategy::parse(raw)?,
+                None => default.strategy,
+            };
+            let spec = ChunkSpec::new(
+                strategy,
+                rule.max_tokens.unwrap_or(default.max_tokens),
+                rule.overlap_tokens.unwrap_or(default.overlap_tokens),
+            );
+            rules.push((matcher, spec));
+        }
+        Ok(Self { rules, default })
+    }
+
+    pub fn resolve(&self, path: &str) -> ChunkSpec {
+        self.rules
+            .iter()
+            .find(|(matcher, _)| matcher.is_match(path))
+            .map(|(_, spec)| *spec)
+            .unwrap_or(self.default)
+    }
+}
+
+/// Split `text` for the text-based strategies. Pieces longer than `max_tokens` are left for the
+/// caller to cut into windows.
+pub fn split(
+    text: &str,
+    strategy: Strategy,
+    max_tokens: usize,
+    tokenizer: &dyn Tokenizer,
+) -> Vec<String> {
+    let count = |piece: &str| tokenizer.spans(piece).len();
+    match strategy {
+        Strategy::Sentence => pack(sentences(text), " ", max_tokens, count),
+        Strategy::Paragraph => pack(paragraphs(text), "\n\n", max_tokens, count),
+        Strategy::Recursive => recursive(text, 0, max_tokens, &count),
+        _ => vec![text.to_string()],
+    }
+}
+
+const SEPARATORS: [&str; 4] = ["\n\n", "\n", ". ", " "];
+
+fn recursive(
+    text: &str,
+    level: usize,
+    max_tokens: usize,
+    count: &dyn Fn(&str) -> usize,
+) -> Vec<String> {
+    let Some(separator) = SEPARATORS.get(level) else {
+        return vec![text.to_string()];
+    };
+    if count(text) <= max_tokens {
+        return vec![text.trim().to_string()];
+    }
+    let pieces = match *separator {
+        "\n\n" => paragraphs(text),
+        ". " => sentences(text),
+        sep => text
+            .split(sep)
+            .map(str::trim)
+            .filter(|piece| !piece.is_empty())
+            .map(str::to_string)
+            .collect(),
+    };
+    let pieces = pieces
+        .iter()
+        .flat_map(|piece| recursive(piece, level + 1, max_tokens, count))
+        .collect();
+    let joiner = if *separator == ". " { " " } else { separator };
+    pack(pieces, joiner, max_tokens, count)
+}
+
+/// Greedily join adjacent pieces while the result stays within `max_tokens`.
+fn pack(
+    pieces: Vec<String>,
+    separator: &str,
+    max_tokens: usize,
+    count: impl Fn(&str) -> usize,
+) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut current = String::new();
+    let mut current_tokens = 0;
+    for piece in pieces {
+        let tokens = count(&piece);
+        if !current.is_empty() && current_tokens + tokens > max_tokens {
+            out.push(std::mem::take(&mut current));
+            current_tokens = 0;
+        }
+        if !current.is_empty() {
+            current.push_str(separator);
+        }
+        current.push_str(&piece);
+        current_tokens += tokens;
+    }
+    if !current.is_empty() {
+        out.push(current);
+    }
+    out
+}
+
+fn paragraphs(text: &str) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut current: Vec<&str> = Vec::new();
+    for line in text.lines() {
+        if line.trim().is_empty() {
+            if !current.is_empty() {
+                out.push(current.join("\n"));
+                current.clear();
+            }
+            continue;
+        }
+        current.push(line);
+    }
+    if !current.is_empty() {
+        out.push(current.join("\n"));
+    }
+    out
+}
+
+/// Sentences end at `.`, `!`, or `?` followed by whitespace, and at blank lines.
+fn sentences(text: &str) -> Vec<String> {
+    let mut out = Vec::new();
+    for paragraph in paragraphs(text) {
+        let mut start = 0;
+        let mut chars = paragraph.char_indices().peekable();
+        while let Some((idx, ch)) = chars.next() {
+            let at_break = matches!(ch, '.' | '!' | '?')
+                && chars.peek().is_some_and(|(_, next)| next.is_whitespace());
+            if at_break {
+                let end = idx + ch.len_utf8();
+                out.push(paragraph[start..end].trim().to_string());
+                start = end;
+            }
+        }
+        let rest = paragraph[start..].trim();
+        if !rest.is_empty() {
+            out.push(rest.to_string());
+        }
+    }
+    out.retain(|sentence| !sentence.is_empty());
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tokenize::WhitespaceTokenizer;
+
+    #[test]
+    fn sentence_strategy_keeps_sentences_whole() {
+        let text = "One two three. Four five six! Seven eight?\n\nNine ten.";
+        let chunks = split(text, Strategy::Sentence, 6, &WhitespaceTokenizer);
+        assert_eq!(
+            chunks,
+            vec!["One two three. Four five six!", "Seven eight? Nine ten."]
+        );
+        let chunks = split(text, Strategy::Recursive, 6, &WhitespaceTokenizer);
+        assert!(chunks.iter().all(|c| c.split_whitespace().count() <= 6));
+    }
+
+    #[test]
+    fn first_matching_rule_wins() -> Result<()> {
+        let config: Config = toml::from_str(
+            "chunk_tokens = 100\noverlap_tokens = 10\n\n[[chunking]]\nglob = \"**/*.md\"\nstrategy = \"heading\"\nmax_tokens = 40\n\n[[chunking]]\nglob = \"**\"\nstrategy = \"paragraph\"\n",
+        )?;
+        let rules = ChunkRules::from_config(&config)?;
+        assert_eq!(
+            rules.resolve("docs/a.md"),
+            ChunkSpec::new(Strategy::Heading, 40, 10)
+        );
+        assert_eq!(rules.resolve("notes.txt").strategy, Strategy::Paragraph);
+        assert_eq!(rules.resolve("notes.txt").max_tokens, 100);
+        Ok(())
+    }
+}
@@ -47,10 +47,17 @@ pub enum Commands {
     /// Add documents to the store
     #[command(
         long_about = "Ingest files or directories into the store.\nUse --glob/--ignore to control scope and --extract-meta for Markdown metadata.",
-        after_help = "Examples:\n  recall add . --glob \"**/*.{md,rs}\" --tag code\n  recall add ./data --glob \"**/*.md\" --extract-meta"
+        after_help = "Examples:\n  recall add . --glob \"**/*.{md,rs}\" --tag code\n  recall add ./data --glob \"**/*.md\" --extract-meta\n  recall add tickets.jsonl --records --id-field id --meta-field priority\n  recall add ./payments --meta owner=payments --meta tier=1\n  cat summary.md | recall add --stdin --as notes/session-42.md --tag notes\n  recall add ./src --git --rev v1.2.0 --tag release\n  recall add . --glob \"**/*.md\" --dry-run --json\n  recall add ./corpus --progress jsonl 2> progress.jsonl\n  recall add ./docs --json --verbose-json"
     )]
     Add(AddArgs),
 
+    /// Watch paths and keep the store in sync
+    #[command(
+        long_about = "Watch files or directories and re-ingest or tombstone them as they change.\nEvents are debounced into batches and reported as JSONL on stdout. Runs until interrupted.",
+        after_help = "Examples:\n  recall watch . --glob \"**/*.md\"\n  recall watch ./src --ignore \"**/target/**\" --debounce-ms 1000"
+    )]
+    Watch(WatchArgs),
+
     /// Remove documents from the store
     #[command(
         long_about = "Remove documents by path or ID. Removals are tombstoned unless --purge is set.",
@@ -58,6 +65,13 @@ pub enum Commands {
     )]
     Rm(RmArgs),
 
+    /// Add or remove document tags
+    #[command(
+        long_about = "Edit document tags without re-ingesting. Target one doc by path or ID, or every doc matching --filter.",
+        after_help = "Examples:\n  recall tag add ./docs/runbook.md oncall\n  recall tag add --filter \"doc.path GLOB '**/api/**'\" api\n  recall tag rm 3f2a9c... draft --json"
+    )]
+    Tag(TagArgs),
+
     /// Hybrid search
     #[command(
         long_about = "Run hybrid search over chunks using semantic + lexical retrieval.\nUse --bm25 or --vector to force a single mode, and --filter for exact constraints.",
@@ -79,6 +93,20 @@ pub enum Commands {
     )]
     Context(ContextArgs),
 
+    /// Find definitions, imports, and calls of a code symbol
+    #[command(
+        long_about = "List where a name is defined, imported, or called in ingested code docs.\nSymbols are extracted line by line when code files are added.",
+        after_help = "Examples:\n  recall symbols search_chunks\n  recall symbols Store --kind def --json"
+    )]
+    Symbols(SymbolsArgs),
+
+    /// List clusters of near-duplicate docs or chunks
+    #[command(
+        long_about = "Group live docs (or chunks) whose SimHash fingerprints differ by at most --distance bits.\nFingerprints are computed at ingest; docs added before they existed are fingerprinted on their next `recall add`.",
+        after_help = "Examples:\n  recall dupes\n  recall dupes --chunks --distance 5 --json"
+    )]
+    Dupes(DupesArgs),
+
     /// Show stats
     Stats {
         /// Output JSON
@@ -98,6 +126,9 @@ pub enum Commands {
         /// Attempt safe repairs
         #[arg(long)]
         fix: bool,
+        /// Stream progress to stderr: jsonl events or a terminal bar
+        #[arg(long, value_name = "MODE", value_parser = ["jsonl", "bar"])]
+        progress: Option<String>,
     },
 
     /// Compact the database
@@ -109,6 +140,9 @@ pub enum Commands {
         /// Output JSON
         #[arg(long)]
         json: bool,
+        /// Stream progress to stderr: jsonl events or a terminal bar
+        #[arg(long, value_name = "MODE", value_parser = ["jsonl", "bar"])]
+        progress: Option<String>,
     },
 
     /// Export the database as JSONL
@@ -143,20 +177,37 @@ pub enum Commands {
 #[derive(Args, Debug)]
 pub struct AddArgs {
     /// Files or directories to add
+    #[arg(conflicts_with_all = ["stdin", "text"])]
     pub paths: Vec<PathBuf>,
 
+    /// Read document text from stdin (requires --as)
+    #[arg(long, requires = "as_path", conflicts_with = "text")]
+    pub stdin: bool,
+
+    /// Inline document text (requires --as)
+    #[arg(long, requires = "as_path")]
+    pub text: Option<String>,
+
+    /// Virtual path recorded as doc.path for --stdin/--text
+    #[arg(long = "as", value_name = "PATH")]
+    pub as_path: Option<String>,
+
     /// Glob to include
     #[arg(long, help = "Include glob pattern")]
     pub glob: Option<String>,
 
-    /// Tag for documents
+    /// Tag for documents (repeatable)
     #[arg(long)]
-    pub tag: Option<String>,
+    pub tag: Vec<String>,
 
     /// Source label
     #[arg(long)]
     pub source: Option<String>,
 
+    /// Metadata stored in doc.meta (key=value, repeatable)
+    #[arg(long, value_name = "KEY=VALUE")]
+    pub meta: Vec<String>,
+
     /// Skip files if mtime unchanged
     #[arg(long)]
     pub mtime_only: bool,
@@ -165,17 +216,151 @@ pub struct AddArgs {
     #[arg(long, help = "Exclude glob pattern (repeatable)")]
     pub ignore: Vec<String>,
 
-    /// Parser hint (auto|plain|markdown|code)
-    #[arg(long, value_parser = ["auto", "plain", "markdown", "code"])]
+    /// Parser hint (auto|plain|markdown|code|notebook)
+    #[arg(long, value_parser = ["auto", "plain", "markdown", "code", "notebook"])]
     pub parser: Option<String>,
 
     /// Extract metadata from markdown headers/front matter
     #[arg(long)]
     pub extract_meta: bool,
 
+    /// Keep text outputs of notebook code cells
+    #[arg(long)]
+    pub notebook_outputs: bool,
+
+    /// Treat .jsonl/.ndjson/.csv/.json files as one document per record
+    #[arg(long)]
+    pub records: bool,
+
+    /// Record field holding the document text
+    #[arg(long, default_value = "text", requires = "records")]
+    pub text_field: String,
+
+    /// Record field used as a stable record ID (defaults to record position)
+    #[arg(long, requires = "records")]
+    pub id_field: Option<String>,
+
+    /// Record field copied into doc.meta (repeatable)
+    #[arg(long, requires = "records")]
+    pub meta_field: Vec<String>,
+
+    /// Record HEAD, last commit, author, and commit time in doc.meta
+    #[arg(long, conflicts_with_all = ["stdin", "text"])]
+    pub git: bool,
+
+    /// Ingest files as of a git revision without checking it out (implies --git)
+    #[arg(long, value_name = "REV", conflicts_with_all = ["stdin", "text"])]
+    pub rev: Option<String>,
+
+    /// Force a text encoding (e.g. latin1, utf-16le) instead of detecting it
+    #[arg(long, value_name = "LABEL")]
+    pub encoding: Option<String>,
+
+    /// Skip files larger than this many bytes
+    #[arg(long, value_name = "BYTES")]
+    pub max_file_bytes: Option<u64>,
+
+    /// Near-duplicate docs: keep, skip, or mark with meta.duplicate_of (default from config)
+    #[arg(long, value_name = "POLICY", value_parser = ["keep", "skip", "mark"])]
+    pub dedup: Option<String>,
+
+    /// Secrets in text: replace with placeholders, skip the file, or off (default from config)
+    #[arg(long, value_name = "POLICY", value_parser = ["replace", "skip", "off"])]
+    pub redact: Option<String>,
+
+    /// Stream progress to stderr: jsonl events or a terminal bar
+    #[arg(long, value_name = "MODE", value_parser = ["jsonl", "bar"])]
+    pub progress: Option<String>,
+
+    /// Report what would be indexed without writing anything
+    #[arg(long)]
+    pub dry_run: bool,
+
     /// Output JSON
     #[arg(long)]
     pub json: bool,
+
+    /// With --json, also list the paths behind each count in `ingest.files`
+    #[arg(long, requires = "json")]
+    pub verbose_json: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct WatchArgs {
+    /// Files or directories to watch
+    pub paths: Vec<PathBuf>,
+
+    /// Glob to include
+    #[arg(long, help = "Include glob pattern")]
+    pub glob: Option<String>,
+
+    /// Ignore globs
+    #[arg(long, help = "Exclude glob pattern (repeatable)")]
+    pub ignore: Vec<String>,
+
+    /// Tag for documents (repeatable)
+    #[arg(long)]
+    pub tag: Vec<String>,
+
+    /// Source label
+    #[arg(long)]
+    pub source: Option<String>,
+
+    /// Metadata stored in doc.meta (key=value, repeatable)
+    #[arg(long, value_name = "KEY=VALUE")]
+    pub meta: Vec<String>,
+
+    /// Parser hint (auto|plain|markdown|code|notebook)
+    #[arg(long, value_parser = ["auto", "plain", "markdown", "code", "notebook"])]
+    pub parser: Option<String>,
+
+    /// Extract metadata from markdown headers/front matter
+    #[arg(long)]
+    pub extract_meta: bool,
+
+    /// Keep text outputs of notebook code cells
+    #[arg(long)]
+    pub notebook_outputs: bool,
+
+    /// Treat .jsonl/.ndjson/.csv/.json files as one document per record
+    #[arg(long)]
+    pub records: bool,
+
+    /// Record field holding the document text
+    #[arg(long, default_value = "text", requires = "records")]
+    pub text_field: String,
+
+    /// Record field used as a stable record ID (defaults to record position)
+    #[arg(long, requires = "records")]
+    pub id_field: Option<String>,
+
+    /// Record field copied into doc.meta (repeatable)
+    #[arg(long, requires = "records")]
+    pub meta_field: Vec<String>,
+
+    /// Record HEAD, last commit, author, and commit time in doc.meta
+    #[arg(long)]
+    pub git: bool,
+
+    /// Force a text encoding (e.g. latin1, utf-16le) instead of detecting it
+    #[arg(long, value_name = "LABEL")]
+    pub encoding: Option<String>,
+
+    /// Skip files larger than this many bytes
+    #[arg(long, value_name = "BYTES")]
+    pub max_file_bytes: Option<u64>,
+
+    /// Near-duplicate docs: keep, skip, or mark with meta.duplicate_of (default from config)
+    #[arg(long, value_name = "POLICY", value_parser = ["keep", "skip", "mark"])]
+    pub dedup: Option<String>,
+
+    /// Secrets in text: replace with placeholders, skip the file, or off (default from config)
+    #[arg(long, value_name = "POLICY", value_parser = ["replace", "skip", "off"])]
+    pub redact: Option<String>,
+
+    /// Quiet period before a batch of changes is applied
+    #[arg(long, default_value_t = 500)]
+    pub debounce_ms: u64,
 }
 
 #[derive(Args, Debug)]
@@ -202,6 +387,10 @@ pub struct SearchArgs {
     )]
     pub filter: Option<String>,
 
+    /// Drop hits that near-duplicate a higher-ranked hit
+    #[arg(long)]
+    pub collapse_dupes: bool,
+
     /// Include explain output
     #[arg(long)]
     pub explain: bool,
@@ -240,6 +429,64 @@ pub struct RmArgs {
     pub json: bool,
 }
 
+#[derive(Args, Debug)]
+pub struct SymbolsArgs {
+    /// Symbol name (exact)
+    pub name: String,
+
+    /// Only definitions, imports, or references (calls)
+    #[arg(long, value_parser = ["def", "import", "ref"])]
+    pub kind: Option<String>,
+
+    /// Output JSON
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct DupesArgs {
+    /// Compare chunks instead of whole docs
+    #[arg(long)]
+    pub chunks: bool,
+
+    /// Maximum differing SimHash bits (default from config)
+    #[arg(long, value_name = "BITS", value_parser = clap::value_parser!(u32).range(0..=32))]
+    pub distance: Option<u32>,
+
+    /// Output JSON
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct TagArgs {
+    #[command(subcommand)]
+    pub action: TagAction,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum TagAction {
+    /// Attach a tag to documents
+    Add(TagEditArgs),
+    /// Detach a tag from documents
+    Rm(TagEditArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct TagEditArgs {
+    /// Document path or ID, then the tag (only the tag with --filter)
+    #[arg(value_name = "DOC|TAG", num_args = 1..=2, required = true)]
+    pub args: Vec<String>,
+
+    /// Select documents with a filter over doc.* fields instead of a path or ID
+    #[arg(long)]
+    pub filter: Option<String>,
+
+    /// Output JSON
+    #[arg(long)]
+    pub json: bool,
+}
+
 #[derive(Args, Debug)]
 pub struct QueryArgs {
     /// RQL string or @file
@@ -291,6 +538,14 @@ pub struct ContextArgs {
     #[arg(long, help = "Cap chunks per doc (default: unlimited)")]
     pub diversity: Option<usize>,
 
+    /// Replace matched chunks with their parent section when it fits the budget
+    #[arg(long, value_parser = ["parent"])]
+    pub expand: Option<String>,
+
+    /// Append chunks of docs linked from the hits, up to N links away
+    #[arg(long, value_name = "N")]
+    pub follow_links: Option<usize>,
+
     /// Output format (text|json)
     #[arg(
         long,
@@ -306,6 +561,10 @@ pub struct ContextArgs {
     )]
     pub filter: Option<String>,
 
+    /// Drop hits that near-duplicate a higher-ranked hit
+    #[arg(long)]
+    pub collapse_dupes: bool,
+
     /// Include explain output
     #[arg(long)]
     pub explain: bool,
@@ -342,6 +601,10 @@ pub struct ImportArgs {
     /// Input file (JSONL)
     pub path: PathBuf,
 
+    /// Stream progress to stderr: jsonl events or a terminal bar
+    #[arg(long, value_name = "MODE", value_parser = ["jsonl", "bar"])]
+    pub progress: Option<String>,
+
     /// Output JSON stats
     #[arg(long)]
     pub json: bool,
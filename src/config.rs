@@ -20,6 +20,9 @@ use anyhow::Result;
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::chunking::ChunkRule;
+use crate::redact::RedactRule;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct Config {
@@ -28,9 +31,23 @@ pub struct Config {
     pub overlap_tokens: usize,
     pub embedding_dim: usize,
     pub embedding: String,
+    pub tokenizer: String,
+    /// Opt-in template for what chunks are embedded as, e.g. `"{title}\n{section}\n\n{text}"`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub embed_template: Option<String>,
     pub bm25_weight: f32,
     pub vector_weight: f32,
     pub max_limit: usize,
+    /// Default `--dedup` policy for `recall add` and `recall watch`: keep, skip, or mark.
+    pub dedup: String,
+    /// SimHash bits two docs or chunks may differ by and still count as near-duplicates.
+    pub dedup_distance: u32,
+    /// Default `--redact` policy for `recall add` and `recall watch`: replace, skip, or off.
+    pub redact: String,
+    /// Per-glob chunking overrides (`[[chunking]]` tables).
+    pub chunking: Vec<ChunkRule>,
+    /// Extra redaction patterns (`[[redact_rules]]` tables), applied after the built-ins.
+    pub redact_rules: Vec<RedactRule>,
 }
 
 impl Default for Config {
@@ -41,9 +58,16 @@ impl Default for Config {
             overlap_tokens: 32,
             embedding_dim: 256,
             embedding: "model2vec".to_string(),
+            tokenizer: "whitespace".to_string(),
+            embed_template: None,
             bm25_weight: 0.5,
             vector_weight: 0.5,
             max_limit: 1000,
+            dedup: "keep".to_string(),
+            dedup_distance: 3,
+            redact: "off".to_string(),
+            chunking: Vec::new(),
+            redact_rules: Vec::new(),
         }
     }
 }
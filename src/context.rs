@@ -15,10 +15,15 @@
 use std::collections::HashMap;
 use std::collections::HashSet;
 
+use anyhow::Result;
 use serde_json::json;
 
 use crate::model::ChunkRow;
+use crate::model::ParentRow;
+use crate::model::ScoredItem;
 use crate::model::SearchResult;
+use crate::store::Store;
+use crate::tokenize::Tokenizer;
 
 #[derive(Debug, Clone)]
 pub struct AssembledContext {
@@ -38,6 +43,23 @@ pub struct ContextChunk {
     pub path: String,
     pub hash: String,
     pub mtime: String,
+    pub title: Option<String>,
+    /// Heading breadcrumb of the matched chunk.
+    pub section: Option<String>,
+    pub meta: Option<serde_json::Value>,
+    /// Set when this entry is a parent section substituted for the matched child chunk.
+    pub child_id: Option<String>,
+    /// Path of the doc whose link pulled this chunk in (`--follow-links`).
+    pub linked_from: Option<String>,
+}
+
+/// Extra material for `assemble` beyond the ranked hits.
+#[derive(Debug, Default)]
+pub struct Expansion {
+    /// Parent sections keyed by child chunk id (`--expand parent`).
+    pub parents: HashMap<String, ParentRow>,
+    /// Chunks of linked docs with the path of the linking doc, packed after the hits.
+    pub linked: Vec<(ScoredItem, String)>,
 }
 
 impl AssembledContext {
@@ -46,36 +68,119 @@ impl AssembledContext {
             "text": self.text,
             "budget_tokens": self.budget_tokens,
             "used_tokens": self.used_tokens,
-            "chunks": self.chunks.iter().map(|c| json!({
-                "id": c.id,
-                "doc_id": c.doc_id,
-                "offset": c.offset,
-                "tokens": c.tokens,
-                "text": c.text,
-                "path": c.path,
-                "hash": c.hash,
-                "mtime": c.mtime,
-            })).collect::<Vec<_>>()
+            "chunks": self.chunks.iter().map(|c| {
+                let mut obj = json!({
+                    "id": c.id,
+                    "doc_id": c.doc_id,
+                    "offset": c.offset,
+                    "tokens": c.tokens,
+                    "text": c.text,
+                    "path": c.path,
+                    "hash": c.hash,
+                    "mtime": c.mtime,
+                });
+                if let Some(title) = &c.title {
+                    obj["title"] = json!(title);
+                }
+                if let Some(section) = &c.section {
+                    obj["section"] = json!(section);
+                }
+                if let Some(meta) = &c.meta {
+                    obj["meta"] = meta.clone();
+                }
+                if let Some(child_id) = &c.child_id {
+                    obj["child_id"] = json!(child_id);
+                }
+                if let Some(linked_from) = &c.linked_from {
+                    obj["linked_from"] = json!(linked_from);
+                }
+                obj
+            }).collect::<Vec<_>>()
         })
     }
 }
 
+/// Docs reachable within `hops` links of the hits, breadth first. Their chunks are interleaved
+/// (every doc's first chunk, then every doc's second, ...) so one long doc cannot crowd out the
+/// rest of the budget.
+pub fn follow_links(
+    store: &Store,
+    result: &SearchResult,
+    hops: usize,
+) -> Result<Vec<(ScoredItem, String)>> {
+    let mut paths: HashMap<String, String> = HashMap::new();
+    let mut frontier = Vec::new();
+    for item in &result.items {
+        if paths
+            .insert(item.doc.id.clone(), item.doc.path.clone())
+            .is_none()
+        {
+            frontier.push(item.doc.id.clone());
+        }
+    }
+
+    let mut linked_docs: Vec<(Vec<ScoredItem>, String)> = Vec::new();
+    for _ in 0..hops {
+        let mut next = Vec::new();
+        for source in &frontier {
+            for target in store.linked_doc_ids(source)? {
+                if paths.contains_key(&target) {
+                    continue;
+                }
+                let items = crate::query::doc_chunks(store, &target)?;
+                let path = items
+                    .first()
+                    .map(|item| item.doc.path.clone())
+                    .unwrap_or_default();
+                paths.insert(target.clone(), path);
+                linked_docs.push((items, paths[source].clone()));
+                next.push(target);
+            }
+        }
+        frontier = next;
+    }
+
+    let mut linked = Vec::new();
+    let longest = linked_docs.iter().map(|(items, _)| items.len()).max();
+    for index in 0..longest.unwrap_or(0) {
+        for (items, from) in &linked_docs {
+            if let Some(item) = items.get(index) {
+                linked.push((item.clone(), from.clone()));
+            }
+        }
+    }
+    Ok(linked)
+}
+
+/// Pack matched chunks, then linked chunks, into `budget_tokens`. A parent section from
+/// `expansion.parents` that fits the remaining budget replaces its child. Each parent is used
+/// once, and never after one of its children was packed on its own, so no text is packed twice.
 pub fn assemble(
     result: &SearchResult,
     budget_tokens: usize,
     diversity: Option<usize>,
+    expansion: &Expansion,
+    tokenizer: &dyn Tokenizer,
 ) -> AssembledContext {
     let mut used = 0usize;
     let mut text_parts = Vec::new();
     let mut chunks = Vec::new();
     let mut seen = HashSet::new();
+    // Parents with a child already packed as a plain chunk.
+    let mut covered = HashSet::new();
     let mut per_doc: HashMap<String, usize> = HashMap::new();
 
-    for item in &result.items {
+    let hits = result.items.iter().map(|item| (item, None));
+    let linked = expansion
+        .linked
+        .iter()
+        .map(|(item, from)| (item, Some(from)));
+    for (item, linked_from) in hits.chain(linked) {
         let Some(chunk) = &item.chunk else {
             continue;
         };
-        if seen.contains(&chunk.id) {
+        let parent = expansion.parents.get(&chunk.id);
+        if seen.contains(&chunk.id) || parent.is_some_and(|parent| seen.contains(&parent.id)) {
             continue;
         }
         let count = per_doc.entry(item.doc.id.clone()).or_default();
@@ -90,26 +195,64 @@ pub fn assemble(
             break;
         }
 
-        let (chunk_text, token_count) = take_tokens(chunk, remaining);
-        if token_count == 0 {
-            continue;
-        }
+        let meta = chunk
+            .meta
+            .as_deref()
+            .and_then(|meta| serde_json::from_str(meta).ok());
+        let expanded = parent
+            .filter(|parent| !covered.contains(&parent.id))
+            .and_then(|parent| {
+                let tokens = tokenizer.spans(&parent.text).len();
+                (tokens > 0 && tokens <= remaining).then_some((parent, tokens))
+            });
+        let entry = match expanded {
+            Some((parent, token_count)) => ContextChunk {
+                id: parent.id.clone(),
+                doc_id: parent.doc_id.clone(),
+                offset: parent.offset,
+                tokens: token_count as i64,
+                text: parent.text.clone(),
+                path: item.doc.path.clone(),
+                hash: item.doc.hash.clone(),
+                mtime: item.doc.mtime.clone(),
+                title: item.doc.title.clone(),
+                section: chunk.section.clone(),
+                meta,
+                child_id: Some(chunk.id.clone()),
+                linked_from: linked_from.cloned(),
+            },
+            None => {
+                let (chunk_text, token_count) = take_tokens(tokenizer, chunk, remaining);
+                if token_count == 0 {
+                    continue;
+                }
+                if let Some(parent) = parent {
+                    covered.insert(parent.id.clone());
+                }
+                ContextChunk {
+                    id: chunk.id.clone(),
+                    doc_id: chunk.doc_id.clone(),
+                    offset: chunk.offset,
+                    tokens: token_count as i64,
+                    text: chunk_text,
+                    path: item.doc.path.clone(),
+                    hash: item.doc.hash.clone(),
+                    mtime: item.doc.mtime.clone(),
+                    title: item.doc.title.clone(),
+                    section: chunk.section.clone(),
+                    meta,
+                    child_id: None,
+                    linked_from: linked_from.cloned(),
+                }
+            }
+        };
 
-        used += token_count;
+        used += entry.tokens as usize;
         *count += 1;
-        seen.insert(chunk.id.clone());
-
-        text_parts.push(chunk_text.clone());
-        chunks.push(ContextChunk {
-            id: chunk.id.clone(),
-            doc_id: chunk.doc_id.clone(),
-            offset: chunk.offset,
-            tokens: token_count as i64,
-            text: chunk_text,
-            path: item.doc.path.clone(),
-            hash: item.doc.hash.clone(),
-            mtime: item.doc.mtime.clone(),
-        });
+        seen.insert(entry.id.clone());
+
+        text_parts.push(entry.text.clone());
+        chunks.push(entry);
     }
 
     AssembledContext {
@@ -120,14 +263,91 @@ pub fn assemble(
     }
 }
 
-fn take_tokens(chunk: &ChunkRow, limit: usize) -> (String, usize) {
-    let tokens: Vec<&str> = chunk.text.split_whitespace().collect();
+fn take_tokens(tokenizer: &dyn Tokenizer, chunk: &ChunkRow, limit: usize) -> (String, usize) {
+    let tokens = tokenizer.spans(&chunk.text);
     if tokens.is_empty() {
         return (String::new(), 0);
     }
     if tokens.len() <= limit {
         return (chunk.text.clone(), tokens.len());
     }
-    let slice = tokens[..limit].join(" ");
+    let slice = tokenizer.join(&chunk.text, &tokens[..limit]);
     (slice, limit)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::DocRow;
+    use crate::output::StatsOut;
+    use crate::tokenize::WhitespaceTokenizer;
+
+    fn hit(id: &str, text: &str) -> ScoredItem {
+        ScoredItem {
+            score: 1.0,
+            lexical: None,
+            semantic: None,
+            doc: DocRow {
+                id: "d".to_string(),
+                path: "a.md".to_string(),
+                mtime: String::new(),
+                hash: String::new(),
+                tag: None,
+                source: None,
+                meta: None,
+                tags: Vec::new(),
+                title: None,
+            },
+            chunk: Some(ChunkRow {
+                id: id.to_string(),
+                doc_id: "d".to_string(),
+                offset: 0,
+                tokens: 0,
+                text: text.to_string(),
+                meta: None,
+                section: None,
+                simhash: None,
+            }),
+        }
+    }
+
+    fn result(items: Vec<ScoredItem>) -> SearchResult {
+        SearchResult {
+            items,
+            stats: StatsOut::default(),
+            filter: None,
+            explain_warnings: Vec::new(),
+            explain: None,
+            selected_fields: None,
+            include_explain: false,
+            limit: 10,
+            offset: 0,
+        }
+    }
+
+    #[test]
+    fn parent_text_is_packed_at_most_once() {
+        let parent = ParentRow {
+            id: "p".to_string(),
+            doc_id: "d".to_string(),
+            offset: 0,
+            text: "one two three four".to_string(),
+        };
+        let mut expansion = Expansion::default();
+        for child in ["a", "b"] {
+            expansion.parents.insert(child.to_string(), parent.clone());
+        }
+
+        // The parent fits: it replaces the first child, and the sibling it covers is dropped.
+        let hits = result(vec![hit("a", "one two"), hit("b", "three four")]);
+        let packed = assemble(&hits, 10, None, &expansion, &WhitespaceTokenizer);
+        let ids: Vec<&str> = packed.chunks.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(ids, ["p"]);
+
+        // A child packed on its own keeps its siblings from pulling in the parent.
+        let packed = assemble(&hits, 3, None, &expansion, &WhitespaceTokenizer);
+        let ids: Vec<&str> = packed.chunks.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(ids, ["a", "b"]);
+        assert_eq!(packed.used_tokens, 3);
+    }
+}
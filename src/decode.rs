@@ -0,0 +1,130 @@
+// Copyright 2026 Recall Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Text decoding for ingestion: BOM handling, charset detection, and binary sniffing.
+
+use anyhow::Result;
+use chardetng::EncodingDetector;
+use encoding_rs::Encoding;
+use encoding_rs::UTF_8;
+use encoding_rs::UTF_16BE;
+use encoding_rs::UTF_16LE;
+
+/// Bytes inspected when sniffing for binary content (same window git uses).
+const SNIFF_BYTES: usize = 8000;
+
+#[derive(Debug)]
+pub struct Decoded {
+    pub text: String,
+    /// Set when the bytes were not plain UTF-8 (transcoded, or carried a BOM).
+    pub encoding: Option<&'static str>,
+    /// True when malformed sequences were replaced with U+FFFD.
+    pub lossy: bool,
+}
+
+pub fn encoding_for_label(label: &str) -> Result<&'static Encoding> {
+    Encoding::for_label(label.trim().as_bytes())
+        .ok_or_else(|| anyhow::anyhow!("unknown encoding: {label}"))
+}
+
+/// Decode `bytes` as text. Returns `None` for binary content.
+pub fn decode(bytes: &[u8], forced: Option<&'static Encoding>) -> Option<Decoded> {
+    if let Some(encoding) = forced {
+        // Single-byte charsets decode anything, so a forced one must not let binaries through.
+        // UTF-16 text is full of NULs, so only it is exempt from sniffing.
+        if encoding != UTF_16LE && encoding != UTF_16BE && looks_binary(bytes) {
+            return None;
+        }
+        let (text, lossy) = encoding.decode_with_bom_removal(bytes);
+        return Some(Decoded {
+            text: text.into_owned(),
+            encoding: Some(encoding.name()),
+            lossy,
+        });
+    }
+
+    if let Some((encoding, bom_len)) = Encoding::for_bom(bytes) {
+        let (text, lossy) = encoding.decode_without_bom_handling(&bytes[bom_len..]);
+        return Some(Decoded {
+            text: text.into_owned(),
+            encoding: Some(encoding.name()),
+            lossy,
+        });
+    }
+
+    if looks_binary(bytes) {
+        return None;
+    }
+
+    if let Ok(text) = std::str::from_utf8(bytes) {
+        return Some(Decoded {
+            text: text.to_string(),
+            encoding: None,
+            lossy: false,
+        });
+    }
+
+    let mut detector = EncodingDetector::new();
+    detector.feed(bytes, true);
+    let encoding = detector.guess(None, true);
+    let (text, lossy) = encoding.decode_without_bom_handling(bytes);
+    Some(Decoded {
+        text: text.into_owned(),
+        encoding: (encoding != UTF_8).then_some(encoding.name()),
+        lossy,
+    })
+}
+
+fn looks_binary(bytes: &[u8]) -> bool {
+    bytes[..bytes.len().min(SNIFF_BYTES)].contains(&0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_bom_legacy_charsets_and_binary() {
+        let utf16: Vec<u8> = [0xFF, 0xFE]
+            .into_iter()
+            .chain("héllo".encode_utf16().flat_map(u16::to_le_bytes))
+            .collect();
+        let decoded = decode(&utf16, None).expect("text");
+        assert_eq!(decoded.text, "héllo");
+        assert_eq!(decoded.encoding, Some("UTF-16LE"));
+
+        let latin1 = b"caf\xe9 cr\xe8me br\xfbl\xe9e, d\xe9j\xe0 vu";
+        let decoded = decode(latin1, None).expect("text");
+        assert_eq!(decoded.text, "café crème brûlée, déjà vu");
+        assert_eq!(decoded.encoding, Some("windows-1252"));
+
+        let plain = decode("plain".as_bytes(), None).expect("text");
+        assert_eq!(plain.encoding, None);
+
+        assert!(decode(b"\x7fELF\x02\x01\x01\x00\x00", None).is_none());
+    }
+
+    #[test]
+    fn forced_encoding_skips_detection() -> Result<()> {
+        let encoding = encoding_for_label("latin1")?;
+        let decoded = decode(b"na\xefve", Some(encoding)).expect("text");
+        assert_eq!(decoded.text, "naïve");
+        assert!(decode(b"\x7fELF\x02\x01\x01\x00\x00", Some(encoding)).is_none());
+        let utf16: Vec<u8> = "hi".encode_utf16().flat_map(u16::to_le_bytes).collect();
+        let utf16le = encoding_for_label("utf-16le")?;
+        assert_eq!(decode(&utf16, Some(utf16le)).expect("text").text, "hi");
+        assert!(encoding_for_label("nope").is_err());
+        Ok(())
+    }
+}
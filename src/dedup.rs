@@ -0,0 +1,290 @@
+// Copyright 2026 Recall Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! SimHash fingerprints for near-duplicate docs and chunks.
+
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+
+use anyhow::Result;
+
+/// Word shingle length fed into the fingerprint.
+const SHINGLE_WORDS: usize = 3;
+
+/// What `recall add` does with a doc that is a near-duplicate of another live doc.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DedupPolicy {
+    /// Ingest it like any other doc.
+    Keep,
+    /// Leave it out of the store.
+    Skip,
+    /// Ingest it with `meta.duplicate_of` set to the other doc's path.
+    Mark,
+}
+
+impl DedupPolicy {
+    pub fn parse(raw: &str) -> Result<Self> {
+        match raw.trim().to_lowercase().as_str() {
+            "keep" => Ok(Self::Keep),
+            "skip" => Ok(Self::Skip),
+            "mark" => Ok(Self::Mark),
+            _ => anyhow::bail!("unsupported dedup policy '{raw}'; supported: keep, skip, mark"),
+        }
+    }
+}
+
+/// 64-bit SimHash over lowercased word shingles. Texts that differ in a few words land a few
+/// bits apart. Text without words has no fingerprint, so stubs never match each other.
+pub fn simhash(text: &str) -> Option<u64> {
+    let words: Vec<String> = text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+    if words.is_empty() {
+        return None;
+    }
+    let mut weights = [0i32; 64];
+    for shingle in words.windows(SHINGLE_WORDS.min(words.len())) {
+        let hash = feature_hash(shingle);
+        for (bit, weight) in weights.iter_mut().enumerate() {
+            if hash >> bit & 1 == 1 {
+                *weight += 1;
+            } else {
+                *weight -= 1;
+            }
+        }
+    }
+    let hash = weights
+        .iter()
+        .enumerate()
+        .filter(|(_, weight)| **weight > 0)
+        .fold(0u64, |acc, (bit, _)| acc | 1 << bit);
+    Some(hash)
+}
+
+pub fn hamming(a: u64, b: u64) -> u32 {
+    (a ^ b).count_ones()
+}
+
+/// FNV-1a over the shingle, then a splitmix64 finalizer so every output bit depends on the input.
+fn feature_hash(shingle: &[String]) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for (index, word) in shingle.iter().enumerate() {
+        if index > 0 {
+            hash ^= u64::from(b' ');
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+        for byte in word.bytes() {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+    }
+    hash = (hash ^ (hash >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    hash = (hash ^ (hash >> 27)).wrapping_mul(0x94d049bb133111eb);
+    hash ^ (hash >> 31)
+}
+
+/// Group `hashes` into clusters of indexes linked by pairs within `distance` bits. Only clusters
+/// with two or more members are returned, each in index order.
+///
+/// Candidates come from splitting the hash into `distance + 1` bands: two hashes within
+/// `distance` bits must agree exactly on at least one band, so only bucket-mates are compared.
+pub fn clusters(hashes: &[u64], distance: u32) -> Vec<Vec<usize>> {
+    let bands = band_count(distance);
+    let mut parent: Vec<usize> = (0..hashes.len()).collect();
+    for band in 0..bands {
+        let mut buckets: HashMap<u64, Vec<usize>> = HashMap::new();
+        for (index, hash) in hashes.iter().enumerate() {
+            buckets
+                .entry(band_value(*hash, band, bands))
+                .or_default()
+                .push(index);
+        }
+        for members in buckets.values().filter(|members| members.len() > 1) {
+            for (i, &a) in members.iter().enumerate() {
+                for &b in &members[i + 1..] {
+                    if hamming(hashes[a], hashes[b]) <= distance {
+                        union(&mut parent, a, b);
+                    }
+                }
+            }
+        }
+    }
+
+    let mut groups: HashMap<usize, Vec<usize>> = HashMap::new();
+    for index in 0..hashes.len() {
+        let root = find(&mut parent, index);
+        groups.entry(root).or_default().push(index);
+    }
+    let mut out: Vec<Vec<usize>> = groups
+        .into_values()
+        .filter(|members| members.len() > 1)
+        .collect();
+    out.sort_by_key(|members| members[0]);
+    out
+}
+
+fn band_count(distance: u32) -> usize {
+    (distance as usize + 1).min(64)
+}
+
+/// Bits of `hash` that fall in `band` out of `bands` equal-width bands.
+fn band_value(hash: u64, band: usize, bands: usize) -> u64 {
+    let start = band * 64 / bands;
+    let width = (band + 1) * 64 / bands - start;
+    let mask = if width == 64 {
+        u64::MAX
+    } else {
+        (1u64 << width) - 1
+    };
+    hash >> start & mask
+}
+
+/// Doc fingerprints by path, bucketed by band so a lookup only compares docs that can be within
+/// `distance` bits. Built once per ingest run instead of scanning every doc per lookup.
+#[derive(Debug)]
+pub struct SimhashIndex {
+    distance: u32,
+    hashes: HashMap<String, u64>,
+    buckets: Vec<HashMap<u64, BTreeSet<String>>>,
+}
+
+impl SimhashIndex {
+    pub fn new(distance: u32) -> Self {
+        Self {
+            distance,
+            hashes: HashMap::new(),
+            buckets: vec![HashMap::new(); band_count(distance)],
+        }
+    }
+
+    pub fn distance(&self) -> u32 {
+        self.distance
+    }
+
+    pub fn insert(&mut self, path: &str, hash: u64) {
+        self.remove(path);
+        let bands = self.buckets.len();
+        for (band, buckets) in self.buckets.iter_mut().enumerate() {
+            buckets
+                .entry(band_value(hash, band, bands))
+                .or_default()
+                .insert(path.to_string());
+        }
+        self.hashes.insert(path.to_string(), hash);
+    }
+
+    pub fn remove(&mut self, path: &str) {
+        let Some(hash) = self.hashes.remove(path) else {
+            return;
+        };
+        let bands = self.buckets.len();
+        for (band, buckets) in self.buckets.iter_mut().enumerate() {
+            let key = band_value(hash, band, bands);
+            if let Some(paths) = buckets.get_mut(&key) {
+                paths.remove(path);
+                if paths.is_empty() {
+                    buckets.remove(&key);
+                }
+            }
+        }
+    }
+
+    /// Nearest path other than `path` within `distance` bits of `hash`, ties broken by path.
+    pub fn nearest(&self, path: &str, hash: u64) -> Option<String> {
+        let bands = self.buckets.len();
+        let mut best: Option<(u32, &String)> = None;
+        for (band, buckets) in self.buckets.iter().enumerate() {
+            let Some(paths) = buckets.get(&band_value(hash, band, bands)) else {
+                continue;
+            };
+            for other in paths.iter().filter(|other| other.as_str() != path) {
+                let bits = hamming(hash, self.hashes[other]);
+                if bits <= self.distance && best.is_none_or(|b| (bits, other) < (b.0, b.1)) {
+                    best = Some((bits, other));
+                }
+            }
+        }
+        best.map(|(_, other)| other.clone())
+    }
+}
+
+fn find(parent: &mut [usize], index: usize) -> usize {
+    let mut root = index;
+    while parent[root] != root {
+        root = parent[root];
+    }
+    let mut current = index;
+    while parent[current] != root {
+        let next = parent[current];
+        parent[current] = root;
+        current = next;
+    }
+    root
+}
+
+fn union(parent: &mut [usize], a: usize, b: usize) {
+    let (a, b) = (find(parent, a), find(parent, b));
+    if a != b {
+        parent[a.max(b)] = a.min(b);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn near_duplicates_hash_close_together() {
+        let base = "Retries use exponential backoff with jitter. The client gives up after five attempts and surfaces the last error to the caller, who decides whether to retry the whole request.";
+        let edited = "Retries use exponential backoff with jitter. The client gives up after six attempts and surfaces the last error to the caller, who decides whether to retry the whole request.";
+        let other = "The index is rebuilt nightly from the object store snapshot, and queries during the rebuild are served from the previous generation until the swap completes.";
+        let (a, b, c) = (
+            simhash(base).expect("words"),
+            simhash(edited).expect("words"),
+            simhash(other).expect("words"),
+        );
+        assert_eq!(simhash(&base.to_uppercase()), Some(a));
+        assert!(hamming(a, b) < hamming(a, c), "{a:x} {b:x} {c:x}");
+        assert!(hamming(a, c) > 10);
+        assert_eq!(simhash("  "), None);
+        assert_eq!(simhash("--- *** ---"), None);
+    }
+
+    #[test]
+    fn clusters_join_transitively_within_distance() {
+        let hashes = [0b0000, 0xffff_0000, 0b0011, 0b0111_0000_0011, 0xffff_0001];
+        assert_eq!(clusters(&hashes, 2), vec![vec![0, 2], vec![1, 4]]);
+        assert_eq!(clusters(&hashes, 3), vec![vec![0, 2, 3], vec![1, 4]]);
+        assert!(clusters(&hashes, 0).is_empty());
+    }
+
+    #[test]
+    fn index_finds_nearest_other_path() {
+        let mut index = SimhashIndex::new(2);
+        index.insert("a.md", 0b0000);
+        index.insert("b.md", 0b0011);
+        index.insert("c.md", 0xffff_0000);
+        assert_eq!(index.nearest("new.md", 0b0001), Some("a.md".to_string()));
+        assert_eq!(index.nearest("a.md", 0b0000), Some("b.md".to_string()));
+        assert_eq!(index.nearest("new.md", 0xff00_ff00), None);
+
+        index.insert("a.md", 0xffff_0001);
+        assert_eq!(index.nearest("new.md", 0b0001), Some("b.md".to_string()));
+        index.remove("b.md");
+        assert_eq!(index.nearest("new.md", 0b0001), None);
+        assert_eq!(index.nearest("c.md", 0xffff_0000), Some("a.md".to_string()));
+    }
+}
@@ -17,19 +17,17 @@ use std::sync::OnceLock;
 
 use anyhow::Context;
 use anyhow::Result;
+use model2vec_rs::model::StaticModel;
 use sha2::Digest;
 use sha2::Sha256;
 use tempfile::TempDir;
 
-use model2vec_rs::model::StaticModel;
-
 use crate::config::Config;
 
 pub const EMBEDDING_HASH: &str = "hash";
 pub const EMBEDDING_MODEL2VEC: &str = "model2vec";
-const MODEL2VEC_SAFETENSORS: &[u8] =
-    include_bytes!("../assets/potion-base-8M/model.safetensors");
-const MODEL2VEC_TOKENIZER: &[u8] = include_bytes!("../assets/potion-base-8M/tokenizer.json");
+const MODEL2VEC_SAFETENSORS: &[u8] = include_bytes!("../assets/potion-base-8M/model.safetensors");
+pub const MODEL2VEC_TOKENIZER: &[u8] = include_bytes!("../assets/potion-base-8M/tokenizer.json");
 const MODEL2VEC_CONFIG: &[u8] = include_bytes!("../assets/potion-base-8M/config.json");
 
 #[derive(Debug, Clone, Copy)]
@@ -174,7 +172,7 @@ fn load_model2vec() -> Result<Model2VecInner> {
         .context("load embedded model2vec")?;
     let dim = model
         .encode(&["".to_string()])
-        .get(0)
+        .first()
         .map(|vec| vec.len())
         .unwrap_or(0);
     if dim == 0 {
@@ -203,6 +201,123 @@ pub fn to_bytes(vec: &[f32]) -> Vec<u8> {
     out
 }
 
+/// A parsed `embed_template`: chunks are embedded as the rendered template while the stored
+/// chunk text stays bare.
+#[derive(Debug, Clone)]
+pub struct EmbedTemplate {
+    raw: String,
+    lines: Vec<Vec<TemplatePart>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TemplateField {
+    Title,
+    Path,
+    Section,
+    Text,
+}
+
+#[derive(Debug, Clone)]
+enum TemplatePart {
+    Literal(String),
+    Field(TemplateField),
+}
+
+/// The values a template's placeholders render to for one chunk.
+pub struct EmbedInput<'a> {
+    pub title: Option<&'a str>,
+    pub path: &'a str,
+    pub section: Option<&'a str>,
+    pub text: &'a str,
+}
+
+impl EmbedTemplate {
+    pub fn from_config(config: &Config) -> Result<Option<Self>> {
+        config
+            .embed_template
+            .as_deref()
+            .map(Self::parse)
+            .transpose()
+    }
+
+    /// `{title}`, `{path}`, `{section}` and `{text}` are placeholders; `{text}` is required.
+    pub fn parse(raw: &str) -> Result<Self> {
+        let mut lines = Vec::new();
+        let mut has_text = false;
+        for line in raw.split('\n') {
+            let mut parts = Vec::new();
+            let mut rest = line;
+            while let Some(open) = rest.find('{') {
+                let Some(close) = rest[open..].find('}') else {
+                    break;
+                };
+                let name = &rest[open + 1..open + close];
+                let field = match name {
+                    "title" => TemplateField::Title,
+                    "path" => TemplateField::Path,
+                    "section" => TemplateField::Section,
+                    "text" => TemplateField::Text,
+                    _ => anyhow::bail!(
+                        "unsupported embed_template placeholder '{{{name}}}'; supported: {{title}}, {{path}}, {{section}}, {{text}}"
+                    ),
+                };
+                has_text |= field == TemplateField::Text;
+                if open > 0 {
+                    parts.push(TemplatePart::Literal(rest[..open].to_string()));
+                }
+                parts.push(TemplatePart::Field(field));
+                rest = &rest[open + close + 1..];
+            }
+            if !rest.is_empty() {
+                parts.push(TemplatePart::Literal(rest.to_string()));
+            }
+            lines.push(parts);
+        }
+        if !has_text {
+            anyhow::bail!("embed_template must contain {{text}}");
+        }
+        Ok(Self {
+            raw: raw.to_string(),
+            lines,
+        })
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.raw
+    }
+
+    /// Lines whose placeholders all render empty, such as `Section: {section}` for a chunk
+    /// outside any heading, are dropped rather than embedded as a bare label.
+    pub fn render(&self, input: &EmbedInput) -> String {
+        let mut out = Vec::with_capacity(self.lines.len());
+        for parts in &self.lines {
+            let mut line = String::new();
+            let mut fields = 0;
+            let mut filled = 0;
+            for part in parts {
+                match part {
+                    TemplatePart::Literal(literal) => line.push_str(literal),
+                    TemplatePart::Field(field) => {
+                        let value = match field {
+                            TemplateField::Title => input.title.unwrap_or(""),
+                            TemplateField::Path => input.path,
+                            TemplateField::Section => input.section.unwrap_or(""),
+                            TemplateField::Text => input.text,
+                        };
+                        fields += 1;
+                        filled += usize::from(!value.is_empty());
+                        line.push_str(value);
+                    }
+                }
+            }
+            if fields == 0 || filled > 0 {
+                out.push(line);
+            }
+        }
+        out.join("\n")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -228,4 +343,30 @@ mod tests {
         };
         assert!(resolve_embedding(&config).is_err());
     }
+
+    #[test]
+    fn embed_template_renders_context_and_drops_empty_lines() {
+        let template = EmbedTemplate::parse("{title} ({path})\nSection: {section}\n\n{text}")
+            .expect("template");
+        let input = EmbedInput {
+            title: Some("Retries"),
+            path: "docs/retries.md",
+            section: None,
+            text: "Set it to 30 seconds.",
+        };
+        assert_eq!(
+            template.render(&input),
+            "Retries (docs/retries.md)\n\nSet it to 30 seconds."
+        );
+        let input = EmbedInput {
+            section: Some("Config > Timeouts"),
+            ..input
+        };
+        assert_eq!(
+            template.render(&input),
+            "Retries (docs/retries.md)\nSection: Config > Timeouts\n\nSet it to 30 seconds."
+        );
+        assert!(EmbedTemplate::parse("{title}").is_err());
+        assert!(EmbedTemplate::parse("{heading} {text}").is_err());
+    }
 }
@@ -0,0 +1,287 @@
+// Copyright 2026 Recall Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Local git metadata for ingestion. Shells out to the `git` CLI; never
+//! touches the network.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Command;
+
+use anyhow::Context;
+use anyhow::Result;
+use serde_json::Map;
+use serde_json::Value;
+use time::OffsetDateTime;
+use time::UtcOffset;
+use time::format_description::well_known::Rfc3339;
+
+#[derive(Debug, Clone)]
+pub struct GitRepo {
+    root: PathBuf,
+}
+
+#[derive(Debug, Clone)]
+pub struct CommitInfo {
+    pub id: String,
+    pub author: String,
+    pub time: String,
+}
+
+impl GitRepo {
+    pub fn discover(path: &Path) -> Result<Self> {
+        let dir = if path.is_dir() {
+            path.to_path_buf()
+        } else {
+            command_dir(path.parent().unwrap_or(Path::new("")))
+        };
+        let out = run_git(&dir, &["rev-parse", "--show-toplevel"])
+            .with_context(|| format!("not a git repository: {}", path.display()))?;
+        Ok(Self {
+            root: PathBuf::from(String::from_utf8_lossy(&out).trim()),
+        })
+    }
+
+    /// Resolve a revision to a full commit id.
+    pub fn resolve(&self, rev: &str) -> Result<String> {
+        let spec = format!("{rev}^{{commit}}");
+        let out = run_git(&self.root, &["rev-parse", "--verify", "--quiet", &spec])
+            .with_context(|| format!("unknown git revision: {rev}"))?;
+        Ok(String::from_utf8_lossy(&out).trim().to_string())
+    }
+
+    /// Last commit reachable from `rev` for every file under `pathspec`, keyed by path relative
+    /// to `dir`. One `git log` pass, newest first, so the first commit seen for a file wins.
+    pub fn last_commits(
+        &self,
+        dir: &Path,
+        rev: &str,
+        pathspec: &str,
+    ) -> Result<HashMap<String, CommitInfo>> {
+        let out = run_git(
+            &command_dir(dir),
+            &[
+                "log",
+                "--relative",
+                "--name-only",
+                "-z",
+                "--format=%x1e%H%x1f%an%x1f%cI",
+                rev,
+                "--",
+                pathspec,
+            ],
+        )?;
+        let out = String::from_utf8_lossy(&out);
+        let mut commits = HashMap::new();
+        for record in out.split('\u{1e}').filter(|record| !record.is_empty()) {
+            let mut fields = record.split('\0');
+            let header = fields.next().unwrap_or_default();
+            let mut parts = header.split('\u{1f}');
+            let (Some(id), Some(author), Some(time)) = (parts.next(), parts.next(), parts.next())
+            else {
+                continue;
+            };
+            let commit = CommitInfo {
+                id: id.to_string(),
+                author: author.to_string(),
+                time: normalize_time(time),
+            };
+            for name in fields.map(|name| name.trim_start_matches('\n')) {
+                if !name.is_empty() && !commits.contains_key(name) {
+                    commits.insert(name.to_string(), commit.clone());
+                }
+            }
+        }
+        Ok(commits)
+    }
+
+    pub fn commit(&self, rev: &str) -> Result<CommitInfo> {
+        let out = run_git(
+            &self.root,
+            &["show", "-s", "--format=%H%x1f%an%x1f%cI", rev],
+        )?;
+        let line = String::from_utf8_lossy(&out);
+        let mut parts = line.trim().split('\u{1f}');
+        Ok(CommitInfo {
+            id: parts.next().unwrap_or_default().to_string(),
+            author: parts.next().unwrap_or_default().to_string(),
+            time: normalize_time(parts.next().unwrap_or_default()),
+        })
+    }
+
+    /// Files in `rev` under `pathspec`, relative to `dir`.
+    pub fn list_files(&self, dir: &Path, rev: &str, pathspec: &str) -> Result<Vec<String>> {
+        let out = run_git(
+            &command_dir(dir),
+            &["ls-tree", "-r", "-z", "--name-only", rev, "--", pathspec],
+        )?;
+        Ok(out
+            .split(|b| *b == 0)
+            .filter(|name| !name.is_empty())
+            .map(|name| String::from_utf8_lossy(name).into_owned())
+            .collect())
+    }
+
+    pub fn read_file(&self, dir: &Path, rev: &str, path: &str) -> Result<Vec<u8>> {
+        run_git(&command_dir(dir), &["show", &format!("{rev}:./{path}")])
+    }
+}
+
+/// Git metadata for the files under one ingest root, loaded up front so per-file lookups do not
+/// spawn `git`.
+#[derive(Debug)]
+pub struct GitFiles {
+    head: String,
+    dir: PathBuf,
+    commits: HashMap<String, CommitInfo>,
+}
+
+impl GitFiles {
+    pub fn load(path: &Path) -> Result<Self> {
+        let repo = GitRepo::discover(path)?;
+        let head = repo.resolve("HEAD")?;
+        let (dir, pathspec) = split_pathspec(path);
+        let commits = repo.last_commits(&dir, &head, &pathspec)?;
+        Ok(Self { head, dir, commits })
+    }
+
+    /// `doc.meta` for a file found under the root; untracked files get only `head`.
+    pub fn doc_meta(&self, path: &Path) -> Map<String, Value> {
+        let commit = path.strip_prefix(&self.dir).ok().and_then(|rel| {
+            self.commits
+                .get(rel.to_string_lossy().replace('\\', "/").as_str())
+        });
+        doc_meta(&self.head, None, commit)
+    }
+}
+
+/// Split an ingest root into the directory git runs in and the pathspec under it.
+pub fn split_pathspec(path: &Path) -> (PathBuf, String) {
+    if path.is_dir() {
+        return (path.to_path_buf(), ".".to_string());
+    }
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| ".".to_string());
+    (path.parent().unwrap_or(Path::new("")).to_path_buf(), name)
+}
+
+pub fn doc_meta(head: &str, rev: Option<&str>, commit: Option<&CommitInfo>) -> Map<String, Value> {
+    let mut meta = Map::new();
+    meta.insert("head".to_string(), head.into());
+    if let Some(rev) = rev {
+        meta.insert("rev".to_string(), rev.into());
+    }
+    if let Some(commit) = commit {
+        meta.insert("last_commit".to_string(), commit.id.clone().into());
+        meta.insert("author".to_string(), commit.author.clone().into());
+        meta.insert("last_commit_time".to_string(), commit.time.clone().into());
+    }
+    meta
+}
+
+/// `git -C ""` is rejected, so map an empty relative dir to ".".
+pub fn command_dir(dir: &Path) -> PathBuf {
+    if dir.as_os_str().is_empty() {
+        PathBuf::from(".")
+    } else {
+        dir.to_path_buf()
+    }
+}
+
+fn run_git(dir: &Path, args: &[&str]) -> Result<Vec<u8>> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .context("run git")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "git {} failed: {}",
+            args.first().copied().unwrap_or_default(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(output.stdout)
+}
+
+/// Commit times are stored in UTC so string comparisons order correctly.
+fn normalize_time(raw: &str) -> String {
+    OffsetDateTime::parse(raw.trim(), &Rfc3339)
+        .ok()
+        .and_then(|t| t.to_offset(UtcOffset::UTC).format(&Rfc3339).ok())
+        .unwrap_or_else(|| raw.trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn commit_times_normalize_to_utc() {
+        assert_eq!(
+            normalize_time("2026-01-02T10:00:00+02:00"),
+            "2026-01-02T08:00:00Z"
+        );
+        assert_eq!(normalize_time("not a time"), "not a time");
+    }
+
+    #[test]
+    fn last_commits_keeps_newest_commit_per_file() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let root = dir.path();
+        let commit = |author: &str, message: &str| -> Result<()> {
+            let name = format!("user.name={author}");
+            run_git(root, &["add", "."])?;
+            run_git(
+                root,
+                &[
+                    "-c",
+                    &name,
+                    "-c",
+                    "user.email=a@example.com",
+                    "commit",
+                    "-qm",
+                    message,
+                ],
+            )?;
+            Ok(())
+        };
+        run_git(root, &["init", "-q"])?;
+        std::fs::create_dir(root.join("docs"))?;
+        std::fs::write(root.join("docs/a.md"), "a")?;
+        std::fs::write(root.join("docs/b c.md"), "b")?;
+        commit("Ada", "one")?;
+        std::fs::write(root.join("docs/a.md"), "a2")?;
+        commit("Grace", "two")?;
+        std::fs::write(root.join("docs/untracked.md"), "u")?;
+
+        let files = GitFiles::load(&root.join("docs"))?;
+        let meta = files.doc_meta(&root.join("docs").join("a.md"));
+        assert_eq!(meta["author"], "Grace");
+        let meta = files.doc_meta(&root.join("docs").join("b c.md"));
+        assert_eq!(meta["author"], "Ada");
+        assert_eq!(meta["head"], files.head.as_str());
+        let meta = files.doc_meta(&root.join("docs").join("untracked.md"));
+        assert!(meta.get("last_commit").is_none());
+
+        // `recall watch` loads one changed file at a time.
+        let file = root.join("docs").join("b c.md");
+        assert_eq!(GitFiles::load(&file)?.doc_meta(&file)["author"], "Ada");
+        Ok(())
+    }
+}
@@ -0,0 +1,291 @@
+// Copyright 2026 Recall Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Offline language detection and the extra FTS terms that make lexical search language-aware.
+
+use rust_stemmers::Algorithm;
+use rust_stemmers::Stemmer;
+
+/// Detection looks at this many leading characters; more rarely changes the answer.
+const DETECT_CHARS: usize = 4096;
+
+/// whatlang's ISO 639-3 codes mapped to the ISO 639-1 codes stored in `doc.lang`.
+const ISO_639_1: [(&str, &str); 69] = [
+    ("afr", "af"),
+    ("aka", "ak"),
+    ("amh", "am"),
+    ("ara", "ar"),
+    ("aze", "az"),
+    ("bel", "be"),
+    ("ben", "bn"),
+    ("bul", "bg"),
+    ("cat", "ca"),
+    ("ces", "cs"),
+    ("cmn", "zh"),
+    ("dan", "da"),
+    ("deu", "de"),
+    ("ell", "el"),
+    ("eng", "en"),
+    ("epo", "eo"),
+    ("est", "et"),
+    ("fin", "fi"),
+    ("fra", "fr"),
+    ("guj", "gu"),
+    ("heb", "he"),
+    ("hin", "hi"),
+    ("hrv", "hr"),
+    ("hun", "hu"),
+    ("hye", "hy"),
+    ("ind", "id"),
+    ("ita", "it"),
+    ("jav", "jv"),
+    ("jpn", "ja"),
+    ("kan", "kn"),
+    ("kat", "ka"),
+    ("khm", "km"),
+    ("kor", "ko"),
+    ("lat", "la"),
+    ("lav", "lv"),
+    ("lit", "lt"),
+    ("mal", "ml"),
+    ("mar", "mr"),
+    ("mkd", "mk"),
+    ("mya", "my"),
+    ("nep", "ne"),
+    ("nld", "nl"),
+    ("nob", "nb"),
+    ("ori", "or"),
+    ("pan", "pa"),
+    ("pes", "fa"),
+    ("pol", "pl"),
+    ("por", "pt"),
+    ("ron", "ro"),
+    ("rus", "ru"),
+    ("sin", "si"),
+    ("slk", "sk"),
+    ("slv", "sl"),
+    ("sna", "sn"),
+    ("spa", "es"),
+    ("srp", "sr"),
+    ("swe", "sv"),
+    ("tam", "ta"),
+    ("tel", "te"),
+    ("tgl", "tl"),
+    ("tha", "th"),
+    ("tuk", "tk"),
+    ("tur", "tr"),
+    ("ukr", "uk"),
+    ("urd", "ur"),
+    ("uzb", "uz"),
+    ("vie", "vi"),
+    ("yid", "yi"),
+    ("zul", "zu"),
+];
+
+/// FTS5 operators that a bare query word must not be rewritten around.
+const FTS_KEYWORDS: [&str; 4] = ["AND", "OR", "NOT", "NEAR"];
+
+/// ISO 639-1 code (639-3 where there is none) of the language `text` is written in, or `None`
+/// when detection is not confident.
+pub fn detect(text: &str) -> Option<&'static str> {
+    let sample = match text.char_indices().nth(DETECT_CHARS) {
+        Some((end, _)) => &text[..end],
+        None => text,
+    };
+    let info = whatlang::detect(sample).filter(|info| info.is_reliable())?;
+    let code = info.lang().code();
+    Some(
+        ISO_639_1
+            .iter()
+            .find(|(long, _)| *long == code)
+            .map_or(code, |(_, short)| short),
+    )
+}
+
+/// Language of a chunk of a doc in `doc_lang`: its own when detection is confident, since docs
+/// can mix languages, else the doc's. Chunks of docs without a language have none.
+pub fn chunk_lang(text: &str, doc_lang: Option<&'static str>) -> Option<&'static str> {
+    doc_lang.map(|doc_lang| detect(text).unwrap_or(doc_lang))
+}
+
+/// Extra tokens indexed next to a chunk's text: stems of words that differ from the word in
+/// `lang`, and overlapping bigrams of CJK runs, which the `unicode61` tokenizer would otherwise
+/// keep as one token per run.
+pub fn index_terms(text: &str, lang: Option<&str>) -> String {
+    let stemmer = lang.and_then(stemmer);
+    let mut terms = Vec::new();
+    for word in text.split(|c: char| !c.is_alphanumeric()) {
+        if word.chars().any(is_cjk) {
+            for (run, cjk) in script_runs(word) {
+                if cjk {
+                    terms.extend(bigrams(run));
+                } else {
+                    terms.push(run.to_lowercase());
+                }
+            }
+        } else if let Some(stemmer) = &stemmer
+            && !word.is_empty()
+        {
+            let lower = word.to_lowercase();
+            let stem = stemmer.stem(&lower);
+            if stem != lower {
+                terms.push(stem.into_owned());
+            }
+        }
+    }
+    terms.join(" ")
+}
+
+/// Rewrite a plain word query so it also matches `index_terms`: each word becomes
+/// `(word OR "stem" ...)` over the stemmers of `langs`, and each CJK run a phrase of its bigrams.
+/// Queries using FTS5 syntax are returned unchanged, as `None`.
+pub fn expand_query(query: &str, langs: &[String]) -> Option<String> {
+    let words: Vec<&str> = query.split_whitespace().collect();
+    let plain = words
+        .iter()
+        .all(|word| word.chars().all(char::is_alphanumeric) && !FTS_KEYWORDS.contains(word));
+    if words.is_empty() || !plain {
+        return None;
+    }
+    let stemmers: Vec<Stemmer> = langs.iter().filter_map(|lang| stemmer(lang)).collect();
+    let mut changed = false;
+    let mut parts = Vec::new();
+    for word in words {
+        if word.chars().any(is_cjk) {
+            changed = true;
+            for (run, cjk) in script_runs(word) {
+                let grams = bigrams(run);
+                if cjk && !grams.is_empty() {
+                    parts.push(format!("\"{}\"", grams.join(" ")));
+                } else {
+                    parts.push(run.to_string());
+                }
+            }
+            continue;
+        }
+        let lower = word.to_lowercase();
+        let mut alternatives = vec![word.to_string()];
+        for stemmer in &stemmers {
+            let stem = format!("\"{}\"", stemmer.stem(&lower));
+            if stem[1..stem.len() - 1] != lower && !alternatives.contains(&stem) {
+                alternatives.push(stem);
+            }
+        }
+        if alternatives.len() > 1 {
+            changed = true;
+            parts.push(format!("({})", alternatives.join(" OR ")));
+        } else {
+            parts.push(word.to_string());
+        }
+    }
+    changed.then(|| parts.join(" "))
+}
+
+fn stemmer(lang: &str) -> Option<Stemmer> {
+    let algorithm = match lang {
+        "ar" => Algorithm::Arabic,
+        "da" => Algorithm::Danish,
+        "de" => Algorithm::German,
+        "el" => Algorithm::Greek,
+        "en" => Algorithm::English,
+        "es" => Algorithm::Spanish,
+        "fi" => Algorithm::Finnish,
+        "fr" => Algorithm::French,
+        "hu" => Algorithm::Hungarian,
+        "it" => Algorithm::Italian,
+        "nb" => Algorithm::Norwegian,
+        "nl" => Algorithm::Dutch,
+        "pt" => Algorithm::Portuguese,
+        "ro" => Algorithm::Romanian,
+        "ru" => Algorithm::Russian,
+        "sv" => Algorithm::Swedish,
+        "ta" => Algorithm::Tamil,
+        "tr" => Algorithm::Turkish,
+        _ => return None,
+    };
+    Some(Stemmer::create(algorithm))
+}
+
+/// Han, kana, and Hangul, where `unicode61` tokens span several words or a word plus particles.
+fn is_cjk(c: char) -> bool {
+    matches!(
+        c,
+        '\u{3040}'..='\u{30FF}'
+            | '\u{3400}'..='\u{4DBF}'
+            | '\u{4E00}'..='\u{9FFF}'
+            | '\u{AC00}'..='\u{D7AF}'
+            | '\u{F900}'..='\u{FAFF}'
+    )
+}
+
+/// Split `word` into maximal runs of CJK and non-CJK characters.
+fn script_runs(word: &str) -> Vec<(&str, bool)> {
+    let mut runs = Vec::new();
+    let mut start = 0;
+    let mut current = None;
+    for (index, c) in word.char_indices() {
+        let cjk = is_cjk(c);
+        if current.is_some_and(|run| run != cjk) {
+            runs.push((&word[start..index], !cjk));
+            start = index;
+        }
+        current = Some(cjk);
+    }
+    if let Some(cjk) = current {
+        runs.push((&word[start..], cjk));
+    }
+    runs
+}
+
+fn bigrams(run: &str) -> Vec<String> {
+    let chars: Vec<char> = run.chars().collect();
+    chars.windows(2).map(|pair| pair.iter().collect()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_language_and_builds_terms() {
+        let english = "The client retries failed requests with exponential backoff and gives up after five attempts.";
+        let german = "Der Client wiederholt fehlgeschlagene Anfragen mit exponentiellem Backoff und gibt nach fünf Versuchen auf.";
+        assert_eq!(detect(english), Some("en"));
+        assert_eq!(detect(german), Some("de"));
+        assert_eq!(detect("ok"), None);
+
+        assert_eq!(index_terms("Retries failed", Some("en")), "retri fail");
+        assert_eq!(index_terms("Retries failed", None), "");
+        assert_eq!(
+            index_terms("東京都の検索API", None),
+            "東京 京都 都の の検 検索 api"
+        );
+    }
+
+    #[test]
+    fn expands_plain_queries_only() {
+        let langs = vec!["en".to_string(), "de".to_string()];
+        assert_eq!(
+            expand_query("retries backoff", &langs).as_deref(),
+            Some("(retries OR \"retri\") backoff")
+        );
+        assert_eq!(
+            expand_query("東京都", &[]).as_deref(),
+            Some("\"東京 京都\"")
+        );
+        assert_eq!(expand_query("backoff", &langs), None);
+        assert_eq!(expand_query("retries OR backoff", &langs), None);
+        assert_eq!(expand_query("retr*", &langs), None);
+    }
+}
@@ -0,0 +1,182 @@
+// Copyright 2026 Recall Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Links between Markdown documents: relative `[text](path)` links and `[[wiki]]` links.
+
+/// Target is a doc path resolved against the linking doc's directory.
+pub const LINK_PATH: &str = "path";
+/// Target is a file name (`Page.md` or `notes/Page.md`) matched against the end of doc paths.
+pub const LINK_WIKI: &str = "wiki";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Link {
+    pub target: String,
+    pub anchor: String,
+    pub kind: &'static str,
+}
+
+/// Links in `text`, skipping fenced code, inline code, images, and external URLs.
+pub fn extract_links(source_path: &str, text: &str) -> Vec<Link> {
+    let mut links = Vec::new();
+    let mut in_code = false;
+    for line in text.lines() {
+        if line.trim_start().starts_with("```") {
+            in_code = !in_code;
+            continue;
+        }
+        if in_code {
+            continue;
+        }
+        // Odd segments between backticks are inline code.
+        for (index, segment) in line.split('`').enumerate() {
+            if index % 2 == 0 {
+                scan_segment(source_path, segment, &mut links);
+            }
+        }
+    }
+    links
+}
+
+fn scan_segment(source_path: &str, segment: &str, links: &mut Vec<Link>) {
+    let mut rest = segment;
+    while let Some(open) = rest.find('[') {
+        let is_image = rest[..open].ends_with('!');
+        let after = &rest[open + 1..];
+        if let Some(inner) = after.strip_prefix('[') {
+            let Some(close) = inner.find("]]") else {
+                return;
+            };
+            if let Some(link) = wiki_link(&inner[..close]) {
+                links.push(link);
+            }
+            rest = &inner[close + 2..];
+            continue;
+        }
+        let Some(close) = after.find(']') else {
+            return;
+        };
+        let anchor = &after[..close];
+        rest = &after[close + 1..];
+        let Some(target) = rest.strip_prefix('(') else {
+            continue;
+        };
+        let Some(end) = target.find(')') else {
+            return;
+        };
+        if !is_image && let Some(resolved) = path_target(source_path, &target[..end]) {
+            links.push(Link {
+                target: resolved,
+                anchor: anchor.trim().to_string(),
+                kind: LINK_PATH,
+            });
+        }
+        rest = &target[end + 1..];
+    }
+}
+
+fn wiki_link(inner: &str) -> Option<Link> {
+    let (target, alias) = match inner.split_once('|') {
+        Some((target, alias)) => (target, Some(alias)),
+        None => (inner, None),
+    };
+    let name = target.split('#').next().unwrap_or("").trim();
+    if name.is_empty() {
+        return None;
+    }
+    let has_ext = name
+        .rsplit('/')
+        .next()
+        .is_some_and(|file| file.contains('.'));
+    let target = if has_ext {
+        name.to_string()
+    } else {
+        format!("{name}.md")
+    };
+    Some(Link {
+        target,
+        anchor: alias.unwrap_or(name).trim().to_string(),
+        kind: LINK_WIKI,
+    })
+}
+
+/// Resolve a relative link target to a doc path; `None` for URLs, absolute paths, and in-page
+/// anchors.
+fn path_target(source_path: &str, raw: &str) -> Option<String> {
+    let raw = raw.trim();
+    let raw = match raw.strip_prefix('<') {
+        Some(inner) => inner.split('>').next().unwrap_or(""),
+        None => raw.split_whitespace().next().unwrap_or(""),
+    };
+    let raw = raw.split(['#', '?']).next().unwrap_or("");
+    let scheme = raw
+        .find(':')
+        .is_some_and(|colon| !raw[..colon].contains('/'));
+    if raw.is_empty() || raw.starts_with('/') || scheme {
+        return None;
+    }
+    let raw = raw.replace("%20", " ");
+
+    let mut parts: Vec<&str> = source_path.split('/').collect();
+    parts.pop();
+    for component in raw.split('/') {
+        match component {
+            "" | "." => {}
+            ".." => match parts.last() {
+                Some(&"..") | None => parts.push(".."),
+                Some(&".") => {
+                    parts.pop();
+                    parts.push("..");
+                }
+                Some(_) => {
+                    parts.pop();
+                }
+            },
+            name => parts.push(name),
+        }
+    }
+    Some(parts.join("/"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_relative_and_wiki_links() {
+        let text = "See [retries](../ops/retry.md#backoff) and [[Runbook|the runbook]].\n\
+                    ![diagram](img.png) [site](https://example.com) [top](#intro)\n\
+                    `[code](x.md)`\n```\n[fenced](y.md)\n```\n[[notes/Setup]]\n";
+        let links = extract_links("./docs/api/client.md", text);
+        assert_eq!(
+            links,
+            vec![
+                Link {
+                    target: "./docs/ops/retry.md".to_string(),
+                    anchor: "retries".to_string(),
+                    kind: LINK_PATH,
+                },
+                Link {
+                    target: "Runbook.md".to_string(),
+                    anchor: "the runbook".to_string(),
+                    kind: LINK_WIKI,
+                },
+                Link {
+                    target: "notes/Setup.md".to_string(),
+                    anchor: "notes/Setup".to_string(),
+                    kind: LINK_WIKI,
+                },
+            ]
+        );
+    }
+}
@@ -12,18 +12,33 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod archive;
+mod chunking;
 mod cli;
 mod config;
 mod context;
+mod decode;
+mod dedup;
 mod embed;
+mod git;
 mod ingest;
+mod lang;
+mod links;
+mod meta;
 mod model;
 mod output;
+mod paths;
+mod progress;
 mod query;
+mod records;
+mod redact;
 mod rql;
 mod sql;
 mod store;
+mod symbols;
+mod tokenize;
 mod transfer;
+mod watch;
 
 use std::io::Read;
 use std::io::Write;
@@ -39,8 +54,11 @@ use serde_json::json;
 use time::OffsetDateTime;
 use time::format_description::well_known::Rfc3339;
 
+use crate::cli::AddArgs;
 use crate::cli::Cli;
 use crate::cli::Commands;
+use crate::cli::TagAction;
+use crate::cli::WatchArgs;
 use crate::config::ConfigCtx;
 use crate::output::JsonResponse;
 use crate::output::MemoryStats;
@@ -48,10 +66,26 @@ use crate::output::StatsOut;
 use crate::output::print_json;
 use crate::store::StoreMode;
 
+/// Some files failed but the rest of the run was committed. Exits with status 2.
+#[derive(Debug)]
+struct PartialFailure(usize);
+
+impl std::fmt::Display for PartialFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            1 => write!(f, "1 file failed to ingest"),
+            n => write!(f, "{n} files failed to ingest"),
+        }
+    }
+}
+
+impl std::error::Error for PartialFailure {}
+
 fn main() {
     if let Err(err) = run() {
         eprintln!("error: {err}");
-        std::process::exit(1);
+        let code = if err.is::<PartialFailure>() { 2 } else { 1 };
+        std::process::exit(code);
     }
 }
 
@@ -59,20 +93,11 @@ fn run() -> Result<()> {
     let cli = Cli::parse();
     match cli.command {
         Commands::Init { path } => cmd_init(path),
-        Commands::Add(args) => handle_result(
-            cmd_add(
-                args.paths,
-                args.glob,
-                args.tag,
-                args.source,
-                args.mtime_only,
-                args.ignore,
-                args.parser,
-                args.extract_meta,
-                args.json,
-            ),
-            args.json,
-        ),
+        Commands::Add(args) => {
+            let json = args.json;
+            handle_result(cmd_add(args), json)
+        }
+        Commands::Watch(args) => cmd_watch(args),
         Commands::Search(args) => handle_result(
             cmd_search(
                 args.query,
@@ -80,6 +105,7 @@ fn run() -> Result<()> {
                 args.bm25,
                 args.vector,
                 args.filter,
+                args.collapse_dupes,
                 args.explain,
                 args.lexical_mode,
                 args.snapshot,
@@ -89,6 +115,16 @@ fn run() -> Result<()> {
             args.json || args.jsonl,
         ),
         Commands::Rm(args) => handle_result(cmd_rm(args.targets, args.purge, args.json), args.json),
+        Commands::Tag(args) => {
+            let (remove, edit) = match args.action {
+                TagAction::Add(edit) => (false, edit),
+                TagAction::Rm(edit) => (true, edit),
+            };
+            handle_result(
+                cmd_tag(remove, edit.args, edit.filter, edit.json),
+                edit.json,
+            )
+        }
         Commands::Query(args) => handle_result(
             cmd_query(
                 args.rql,
@@ -106,8 +142,11 @@ fn run() -> Result<()> {
                 args.query,
                 args.budget_tokens,
                 args.diversity,
+                args.expand,
+                args.follow_links,
                 args.format,
                 args.filter,
+                args.collapse_dupes,
                 args.explain,
                 args.lexical_mode,
                 args.snapshot,
@@ -115,11 +154,23 @@ fn run() -> Result<()> {
             ),
             args.json,
         ),
+        Commands::Symbols(args) => {
+            handle_result(cmd_symbols(args.name, args.kind, args.json), args.json)
+        }
+        Commands::Dupes(args) => {
+            handle_result(cmd_dupes(args.chunks, args.distance, args.json), args.json)
+        }
         Commands::Stats { json } => handle_result(cmd_stats(json), json),
-        Commands::Doctor { json, fix } => handle_result(cmd_doctor(json, fix), json),
-        Commands::Compact { json } => handle_result(cmd_compact(json), json),
+        Commands::Doctor {
+            json,
+            fix,
+            progress,
+        } => handle_result(cmd_doctor(json, fix, progress), json),
+        Commands::Compact { json, progress } => handle_result(cmd_compact(json, progress), json),
         Commands::Export(args) => handle_result(cmd_export(args.out, args.json), args.json),
-        Commands::Import(args) => handle_result(cmd_import(args.path, args.json), args.json),
+        Commands::Import(args) => {
+            handle_result(cmd_import(args.path, args.progress, args.json), args.json)
+        }
         Commands::Completions { shell } => handle_result(cmd_completions(shell), false),
         Commands::Guide => handle_result(cmd_guide(), false),
     }
@@ -128,6 +179,7 @@ fn run() -> Result<()> {
 fn handle_result(result: Result<()>, json: bool) -> Result<()> {
     match result {
         Ok(()) => Ok(()),
+        Err(err) if err.is::<PartialFailure>() => Err(err),
         Err(err) => {
             if json {
                 let resp = JsonResponse::error("error", &err.to_string());
@@ -155,49 +207,208 @@ fn cmd_init(path: Option<PathBuf>) -> Result<()> {
     Ok(())
 }
 
-#[allow(clippy::too_many_arguments)]
-fn cmd_add(
-    paths: Vec<PathBuf>,
-    glob: Option<String>,
-    tag: Option<String>,
-    source: Option<String>,
-    mtime_only: bool,
-    ignore: Vec<String>,
-    parser: Option<String>,
-    extract_meta: bool,
-    json: bool,
-) -> Result<()> {
+fn cmd_add(args: AddArgs) -> Result<()> {
+    let AddArgs {
+        paths,
+        stdin,
+        text,
+        as_path,
+        glob,
+        tag,
+        source,
+        meta,
+        mtime_only,
+        ignore,
+        parser,
+        extract_meta,
+        notebook_outputs,
+        records,
+        text_field,
+        id_field,
+        meta_field: meta_fields,
+        git,
+        rev,
+        encoding,
+        max_file_bytes,
+        dedup,
+        redact,
+        progress,
+        dry_run,
+        json,
+        verbose_json,
+    } = args;
     let ctx = ConfigCtx::load_from_cwd()?;
-    let store = store::Store::open(&ctx.store_path(), StoreMode::ReadWrite, &ctx.config)?;
+    let progress = open_progress(progress)?;
+    let mode = if dry_run {
+        StoreMode::ReadOnly
+    } else {
+        StoreMode::ReadWrite
+    };
+    let store = store::Store::open(&ctx.store_path(), mode, &ctx.config)?;
 
     let parser_hint = parse_parser_hint(parser.as_deref())?;
     let opts = ingest::IngestOptions {
         glob,
-        tag,
+        tags: normalize_tags(tag)?,
         source,
         mtime_only,
         ignore,
         parser: parser_hint,
         extract_meta,
+        notebook_outputs,
+        records: records.then_some(records::RecordOptions {
+            text_field,
+            id_field,
+            meta_fields,
+        }),
+        git: git || rev.is_some(),
+        rev,
+        encoding: encoding
+            .as_deref()
+            .map(decode::encoding_for_label)
+            .transpose()?,
+        meta: meta::parse_meta_args(&meta)?,
+        dry_run,
+        max_file_bytes,
+        dedup: dedup::DedupPolicy::parse(dedup.as_deref().unwrap_or(&ctx.config.dedup))?,
+        dedup_distance: ctx.config.dedup_distance,
+        redact: redact::RedactPolicy::parse(redact.as_deref().unwrap_or(&ctx.config.redact))?,
     };
 
-    let report = ingest::ingest_paths(&store, &ctx.config, paths, opts)?;
+    let inline_text = if stdin {
+        let mut buf = Vec::new();
+        std::io::stdin()
+            .read_to_end(&mut buf)
+            .context("read document from stdin")?;
+        Some(buf)
+    } else {
+        text.map(String::into_bytes)
+    };
+    let report = match (inline_text, as_path) {
+        (Some(_), Some(virtual_path)) if virtual_path.trim().is_empty() => {
+            anyhow::bail!("--as requires a non-empty path")
+        }
+        (Some(text), Some(virtual_path)) => {
+            ingest::ingest_text(&store, &ctx.config, &virtual_path, &text, opts)?
+        }
+        (None, Some(_)) => anyhow::bail!("--as requires --stdin or --text"),
+        _ => ingest::ingest_paths(&store, &ctx.config, paths, opts, &progress)?,
+    };
+    progress.finish();
 
+    if dry_run {
+        return print_ingest_plan(report, json);
+    }
+    let failed = report.errors.len();
+    let summary = report.summary(verbose_json);
     if json {
         let resp = JsonResponse::ok()
             .with_stats(report.stats())
-            .with_warnings(report.warnings);
+            .with_ingest(serde_json::to_value(&summary)?)
+            .with_warnings(report.warnings)
+            .with_errors(report.errors)
+            .with_redactions(report.redactions);
         print_json(&resp)?;
     } else {
+        use ingest::FileOutcome;
         println!(
             "Added {} docs ({} chunks)",
             report.docs_added, report.chunks_added
         );
+        let unchanged =
+            summary.count(FileOutcome::UnchangedMtime) + summary.count(FileOutcome::UnchangedHash);
+        let skipped = summary.count(FileOutcome::SkippedGlob)
+            + summary.count(FileOutcome::SkippedIgnore)
+            + summary.count(FileOutcome::SkippedBinary)
+            + summary.count(FileOutcome::SkippedOther);
+        println!(
+            "{} new, {} updated, {unchanged} unchanged, {skipped} skipped, {} removed; {} chunks tombstoned, {} bytes read, {} ms embedding",
+            summary.count(FileOutcome::New),
+            summary.count(FileOutcome::Updated),
+            summary.count(FileOutcome::Removed),
+            summary.chunks_tombstoned,
+            summary.bytes_read,
+            summary.embed_ms,
+        );
         for warn in report.warnings {
             eprintln!("warning: {warn}");
         }
+        for err in report.errors {
+            eprintln!("error: {}: {}", err.path, err.message);
+        }
     }
 
+    if failed > 0 {
+        return Err(PartialFailure(failed).into());
+    }
+    Ok(())
+}
+
+fn print_ingest_plan(report: ingest::IngestReport, json: bool) -> Result<()> {
+    use ingest::FileOutcome;
+    let failed = report.errors.len();
+    let count = |action: &str| {
+        report
+            .plan
+            .iter()
+            .filter(|e| e.outcome.action() == action)
+            .count()
+    };
+    let (new, updated, unchanged, skipped, removed) = (
+        count("new"),
+        count("updated"),
+        count("unchanged"),
+        count("skipped"),
+        count("removed"),
+    );
+    let chunks: usize = report
+        .plan
+        .iter()
+        .filter(|e| matches!(e.outcome, FileOutcome::New | FileOutcome::Updated))
+        .filter_map(|e| e.chunks)
+        .sum();
+
+    if json {
+        let resp = JsonResponse::ok()
+            .with_plan(json!({
+                "files": report.plan,
+                "summary": {
+                    "new": new,
+                    "updated": updated,
+                    "unchanged": unchanged,
+                    "skipped": skipped,
+                    "removed": removed,
+                    "chunks": chunks,
+                },
+            }))
+            .with_warnings(report.warnings)
+            .with_errors(report.errors)
+            .with_redactions(report.redactions);
+        print_json(&resp)?;
+    } else {
+        for entry in &report.plan {
+            let detail = match (&entry.reason, entry.parser, entry.strategy, entry.chunks) {
+                (Some(reason), ..) => reason.clone(),
+                (None, Some(parser), Some(strategy), Some(chunks)) => {
+                    format!("{parser}, {strategy}, {chunks} chunks")
+                }
+                _ => String::new(),
+            };
+            println!("{}\t{}\t{}", entry.outcome.action(), entry.path, detail);
+        }
+        println!(
+            "Dry run: {new} new, {updated} updated, {unchanged} unchanged, {skipped} skipped, {removed} removed ({chunks} chunks); nothing written"
+        );
+        for warn in report.warnings {
+            eprintln!("warning: {warn}");
+        }
+        for err in report.errors {
+            eprintln!("error: {}: {}", err.path, err.message);
+        }
+    }
+    if failed > 0 {
+        return Err(PartialFailure(failed).into());
+    }
     Ok(())
 }
 
@@ -206,15 +417,13 @@ fn cmd_rm(targets: Vec<String>, purge: bool, json: bool) -> Result<()> {
     let store = store::Store::open(&ctx.store_path(), StoreMode::ReadWrite, &ctx.config)?;
     let mut removed = 0usize;
     for target in targets {
-        if std::path::Path::new(&target).exists() || target.contains(std::path::MAIN_SEPARATOR) {
-            removed += store.mark_doc_deleted(&target)?.len();
-        } else {
-            removed += store.mark_doc_deleted_by_id(&target)?;
+        for id in store.resolve_docs(&target)? {
+            removed += store.mark_doc_deleted_by_id(&id)?;
         }
     }
 
     if purge {
-        store.compact()?;
+        store.compact(&progress::Progress::off())?;
     }
 
     if json {
@@ -236,6 +445,208 @@ fn cmd_rm(targets: Vec<String>, purge: bool, json: bool) -> Result<()> {
     Ok(())
 }
 
+fn cmd_symbols(name: String, kind: Option<String>, json: bool) -> Result<()> {
+    let ctx = ConfigCtx::load_from_cwd()?;
+    let store = store::Store::open(&ctx.store_path(), StoreMode::ReadOnly, &ctx.config)?;
+    let hits = store.find_symbols(&name, kind.as_deref())?;
+
+    if json {
+        let results = hits
+            .iter()
+            .map(serde_json::to_value)
+            .collect::<Result<Vec<_>, _>>()?;
+        let resp = JsonResponse::ok()
+            .with_results(results)
+            .with_stats(StatsOut {
+                took_ms: 0,
+                total_hits: hits.len() as i64,
+                doc_count: None,
+                chunk_count: None,
+                db_size_bytes: None,
+                snapshot: store.snapshot_token().ok(),
+                timings: None,
+                corpus: None,
+                memory: None,
+            });
+        print_json(&resp)?;
+    } else if hits.is_empty() {
+        println!("No symbols named {name}");
+    } else {
+        for hit in &hits {
+            println!("{:<6} {}:{}  {}", hit.kind, hit.path, hit.line, hit.text);
+        }
+    }
+    Ok(())
+}
+
+fn cmd_dupes(chunks: bool, distance: Option<u32>, json: bool) -> Result<()> {
+    let ctx = ConfigCtx::load_from_cwd()?;
+    let store = store::Store::open(&ctx.store_path(), StoreMode::ReadOnly, &ctx.config)?;
+    let distance = distance.unwrap_or(ctx.config.dedup_distance);
+    let members = if chunks {
+        store.chunk_fingerprints()?
+    } else {
+        store.doc_fingerprints()?
+    };
+    let hashes: Vec<u64> = members.iter().map(|member| member.simhash).collect();
+    let mut clusters = dedup::clusters(&hashes, distance);
+    // Largest first; members are already in path order.
+    clusters.sort_by_key(|cluster| std::cmp::Reverse(cluster.len()));
+    let unit = if chunks { "chunks" } else { "docs" };
+
+    if json {
+        let results = clusters
+            .iter()
+            .map(|cluster| {
+                let members: Vec<_> = cluster.iter().map(|&index| &members[index]).collect();
+                json!({ "size": cluster.len(), "members": members })
+            })
+            .collect();
+        let resp = JsonResponse::ok()
+            .with_results(results)
+            .with_stats(StatsOut {
+                took_ms: 0,
+                total_hits: clusters.len() as i64,
+                doc_count: None,
+                chunk_count: None,
+                db_size_bytes: None,
+                snapshot: store.snapshot_token().ok(),
+                timings: None,
+                corpus: None,
+                memory: None,
+            });
+        print_json(&resp)?;
+    } else if clusters.is_empty() {
+        println!("No near-duplicate {unit}");
+    } else {
+        for (index, cluster) in clusters.iter().enumerate() {
+            if index > 0 {
+                println!();
+            }
+            println!("{} near-duplicate {unit}", cluster.len());
+            for member in cluster.iter().map(|&index| &members[index]) {
+                match (member.offset, &member.text) {
+                    (Some(offset), Some(text)) => {
+                        let snippet: String = text.chars().take(60).collect();
+                        println!("  {} @{offset}  {snippet}", member.path);
+                    }
+                    _ => println!("  {}", member.path),
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn cmd_tag(remove: bool, args: Vec<String>, filter: Option<String>, json: bool) -> Result<()> {
+    let ctx = ConfigCtx::load_from_cwd()?;
+    let store = store::Store::open(&ctx.store_path(), StoreMode::ReadWrite, &ctx.config)?;
+    let (doc_ids, tag) = match (load_filter(filter)?, args.as_slice()) {
+        (Some(filter), [tag]) => (query::filter_doc_ids(&store, &filter)?, tag),
+        (None, [target, tag]) => {
+            let ids = store.resolve_docs(target)?;
+            if ids.is_empty() {
+                anyhow::bail!("no live document with path or ID {target}");
+            }
+            (ids, tag)
+        }
+        (Some(_), _) => anyhow::bail!("with --filter, pass only the tag"),
+        (None, _) => anyhow::bail!("expected a document path or ID and a tag"),
+    };
+    let tag = normalize_tags(vec![tag.clone()])?.remove(0);
+
+    let tx = store.conn.unchecked_transaction()?;
+    let mut changed = 0usize;
+    for doc_id in &doc_ids {
+        let updated = if remove {
+            store.remove_doc_tag(doc_id, &tag)?
+        } else {
+            store.add_doc_tag(doc_id, &tag)?
+        };
+        changed += usize::from(updated);
+    }
+    tx.commit()?;
+
+    if json {
+        let resp = JsonResponse::ok().with_stats(StatsOut {
+            took_ms: 0,
+            total_hits: changed as i64,
+            doc_count: Some(doc_ids.len() as i64),
+            chunk_count: None,
+            db_size_bytes: None,
+            snapshot: store.snapshot_token().ok(),
+            timings: None,
+            corpus: None,
+            memory: None,
+        });
+        print_json(&resp)?;
+    } else if remove {
+        println!("Removed tag {tag} from {changed} of {} docs", doc_ids.len());
+    } else {
+        println!("Added tag {tag} to {changed} of {} docs", doc_ids.len());
+    }
+    Ok(())
+}
+
+fn cmd_watch(args: WatchArgs) -> Result<()> {
+    let WatchArgs {
+        paths,
+        glob,
+        ignore,
+        tag,
+        source,
+        meta,
+        parser,
+        extract_meta,
+        notebook_outputs,
+        records,
+        text_field,
+        id_field,
+        meta_field: meta_fields,
+        git,
+        encoding,
+        max_file_bytes,
+        dedup,
+        redact,
+        debounce_ms,
+    } = args;
+    let ctx = ConfigCtx::load_from_cwd()?;
+    let opts = ingest::IngestOptions {
+        glob,
+        tags: normalize_tags(tag)?,
+        source,
+        mtime_only: false,
+        ignore,
+        parser: parse_parser_hint(parser.as_deref())?,
+        extract_meta,
+        notebook_outputs,
+        records: records.then_some(records::RecordOptions {
+            text_field,
+            id_field,
+            meta_fields,
+        }),
+        git,
+        rev: None,
+        encoding: encoding
+            .as_deref()
+            .map(decode::encoding_for_label)
+            .transpose()?,
+        meta: meta::parse_meta_args(&meta)?,
+        dry_run: false,
+        max_file_bytes,
+        dedup: dedup::DedupPolicy::parse(dedup.as_deref().unwrap_or(&ctx.config.dedup))?,
+        dedup_distance: ctx.config.dedup_distance,
+        redact: redact::RedactPolicy::parse(redact.as_deref().unwrap_or(&ctx.config.redact))?,
+    };
+    watch::watch(
+        &ctx.store_path(),
+        &ctx.config,
+        paths,
+        opts,
+        std::time::Duration::from_millis(debounce_ms),
+    )
+}
+
 #[allow(clippy::too_many_arguments)]
 fn cmd_search(
     query: String,
@@ -243,6 +654,7 @@ fn cmd_search(
     bm25: bool,
     vector: bool,
     filter: Option<String>,
+    collapse_dupes: bool,
     explain: bool,
     lexical_mode: String,
     snapshot: Option<String>,
@@ -264,6 +676,7 @@ fn cmd_search(
         explain,
         lexical_mode,
         snapshot: snapshot.clone(),
+        collapse_dupes,
     };
 
     let result = query::search_chunks(&store, &ctx.config, &query, opts)?;
@@ -419,11 +832,14 @@ fn cmd_export(out: Option<PathBuf>, json: bool) -> Result<()> {
     Ok(())
 }
 
-fn cmd_import(path: PathBuf, json: bool) -> Result<()> {
+fn cmd_import(path: PathBuf, progress: Option<String>, json: bool) -> Result<()> {
     let ctx = ConfigCtx::load_from_cwd()?;
+    let progress = open_progress(progress)?;
     let store = store::Store::open(&ctx.store_path(), StoreMode::ReadWrite, &ctx.config)?;
     let file = std::fs::File::open(&path).with_context(|| format!("open {}", path.display()))?;
-    let stats = transfer::import_store(&store, file)?;
+    progress.import_started(file.metadata().ok().map(|metadata| metadata.len()));
+    let stats = transfer::import_store(&store, file, &progress)?;
+    progress.finish();
 
     if json {
         let resp = JsonResponse::ok().with_stats(StatsOut {
@@ -447,8 +863,11 @@ fn cmd_context(
     query: String,
     budget_tokens: usize,
     diversity: Option<usize>,
+    expand: Option<String>,
+    follow_links: Option<usize>,
     format: Option<String>,
     filter: Option<String>,
+    collapse_dupes: bool,
     explain: bool,
     lexical_mode: String,
     snapshot: Option<String>,
@@ -482,11 +901,32 @@ fn cmd_context(
         explain,
         lexical_mode,
         snapshot: snapshot.clone(),
+        collapse_dupes,
     };
 
     let search = query::search_chunks(&store, &ctx.config, &query, opts)?;
     let assemble_start = std::time::Instant::now();
-    let assembled = context::assemble(&search, budget_tokens, diversity);
+    let tokenizer = tokenize::build_tokenizer(&ctx.config)?;
+    let mut expansion = context::Expansion::default();
+    if let Some(hops) = follow_links {
+        expansion.linked = context::follow_links(&store, &search, hops)?;
+    }
+    if expand.as_deref() == Some("parent") {
+        let hits = search.items.iter();
+        let linked = expansion.linked.iter().map(|(item, _)| item);
+        for chunk in hits.chain(linked).filter_map(|item| item.chunk.as_ref()) {
+            if let Some(parent) = store.chunk_parent(&chunk.id)? {
+                expansion.parents.insert(chunk.id.clone(), parent);
+            }
+        }
+    }
+    let assembled = context::assemble(
+        &search,
+        budget_tokens,
+        diversity,
+        &expansion,
+        tokenizer.as_ref(),
+    );
     let assemble_ms = assemble_start.elapsed().as_millis() as i64;
     let mut stats = search.stats.clone();
     if let Some(timings) = stats.timings.as_mut() {
@@ -550,34 +990,43 @@ fn cmd_stats(json: bool) -> Result<()> {
         println!("Chunks: {}", stats.chunk_count);
         println!("DB size: {} bytes", stats.db_size_bytes);
         println!("Embedding: {}", ctx.config.embedding);
+        println!("Tokenizer: {}", ctx.config.tokenizer);
+        if let Some(template) = &ctx.config.embed_template {
+            println!("Embed template: {}", template.escape_debug());
+        }
     }
 
     Ok(())
 }
 
-fn cmd_doctor(json: bool, fix: bool) -> Result<()> {
+fn cmd_doctor(json: bool, fix: bool, progress: Option<String>) -> Result<()> {
     let ctx = ConfigCtx::load_from_cwd()?;
+    let progress = open_progress(progress)?;
     let mode = if fix {
         StoreMode::ReadWrite
     } else {
         StoreMode::ReadOnly
     };
     let store = store::Store::open(&ctx.store_path(), mode, &ctx.config)?;
+    progress.phase("check");
     let report = store.integrity_check()?;
     let mut consistency = store.consistency_report()?;
     let mut actions = Vec::new();
 
     if fix {
         if !consistency.fts_ok() {
+            progress.phase("rebuild_fts");
             store.rebuild_fts()?;
             actions.push("rebuild fts index".to_string());
         }
         if !consistency.vec_ok() {
+            progress.phase("rebuild_vec");
             let rebuilt = store.rebuild_vec()?;
             actions.push(format!("rebuild chunk_vec ({rebuilt} entries)"));
         }
         consistency = store.consistency_report()?;
     }
+    progress.finish();
 
     let stats_out = StatsOut {
         took_ms: 0,
@@ -637,9 +1086,11 @@ fn cmd_doctor(json: bool, fix: bool) -> Result<()> {
     Ok(())
 }
 
-fn cmd_compact(json: bool) -> Result<()> {
+fn cmd_compact(json: bool, progress: Option<String>) -> Result<()> {
     let ctx = ConfigCtx::load_from_cwd()?;
+    let progress = open_progress(progress)?;
     let store = store::Store::open(&ctx.store_path(), StoreMode::ReadWrite, &ctx.config)?;
+    progress.phase("check");
     let integrity = store.integrity_check()?;
     let consistency = store.consistency_report()?;
     if integrity.status != "ok" || !consistency.fts_ok() || !consistency.vec_ok() {
@@ -650,7 +1101,8 @@ fn cmd_compact(json: bool) -> Result<()> {
             consistency.vec_missing
         );
     }
-    store.compact()?;
+    store.compact(&progress)?;
+    progress.finish();
 
     if json {
         let resp = JsonResponse::ok().with_actions(vec![
@@ -679,6 +1131,29 @@ fn cmd_guide() -> Result<()> {
     Ok(())
 }
 
+fn open_progress(mode: Option<String>) -> Result<progress::Progress> {
+    let mode = mode
+        .as_deref()
+        .map(progress::ProgressMode::parse)
+        .transpose()?;
+    Ok(progress::Progress::new(mode))
+}
+
+/// Trim tags, reject empty ones, and drop duplicates while keeping the given order.
+fn normalize_tags(tags: Vec<String>) -> Result<Vec<String>> {
+    let mut out: Vec<String> = Vec::new();
+    for tag in tags {
+        let tag = tag.trim();
+        if tag.is_empty() {
+            anyhow::bail!("tags must not be empty");
+        }
+        if !out.iter().any(|t| t == tag) {
+            out.push(tag.to_string());
+        }
+    }
+    Ok(out)
+}
+
 fn parse_parser_hint(input: Option<&str>) -> Result<ingest::ParserHint> {
     let hint = input.unwrap_or("auto");
     let parsed = match hint {
@@ -686,6 +1161,7 @@ fn parse_parser_hint(input: Option<&str>) -> Result<ingest::ParserHint> {
         "plain" => ingest::ParserHint::Plain,
         "markdown" => ingest::ParserHint::Markdown,
         "code" => ingest::ParserHint::Code,
+        "notebook" => ingest::ParserHint::Notebook,
         _ => anyhow::bail!("unknown parser hint: {hint}"),
     };
     Ok(parsed)
@@ -778,15 +1254,15 @@ fn memory_stats() -> MemoryStats {
         let mut stats = MemoryStats::default();
         if let Ok(text) = std::fs::read_to_string("/proc/self/statm") {
             let mut parts = text.split_whitespace();
-            if let (Some(size), Some(rss)) = (parts.next(), parts.next()) {
-                if let (Ok(size), Ok(rss)) = (size.parse::<u64>(), rss.parse::<u64>()) {
-                    let page_size = 4096u64;
-                    stats.virt_bytes = Some(size.saturating_mul(page_size));
-                    stats.rss_bytes = Some(rss.saturating_mul(page_size));
-                }
+            if let (Some(size), Some(rss)) = (parts.next(), parts.next())
+                && let (Ok(size), Ok(rss)) = (size.parse::<u64>(), rss.parse::<u64>())
+            {
+                let page_size = 4096u64;
+                stats.virt_bytes = Some(size.saturating_mul(page_size));
+                stats.rss_bytes = Some(rss.saturating_mul(page_size));
             }
         }
-        return stats;
+        stats
     }
 
     #[cfg(not(target_os = "linux"))]
@@ -0,0 +1,168 @@
+// Copyright 2026 Recall Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Metadata attached at ingest time: `--meta key=value` arguments, per-file
+//! `<file>.meta.json` sidecars, and per-directory `.recallmeta` files.
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::Result;
+
+use crate::ingest::normalize_meta_key;
+
+pub type MetaMap = serde_json::Map<String, serde_json::Value>;
+
+pub const SIDECAR_SUFFIX: &str = ".meta.json";
+pub const DIR_META_FILE: &str = ".recallmeta";
+
+/// Parse `key=value`. Values that parse as JSON keep their type (`tier=1` is a number);
+/// anything else is stored as a string.
+fn parse_meta_arg(raw: &str) -> Result<(String, serde_json::Value)> {
+    let (key, value) = raw
+        .split_once('=')
+        .ok_or_else(|| anyhow::anyhow!("--meta expects key=value: {raw}"))?;
+    let key =
+        normalize_meta_key(key).ok_or_else(|| anyhow::anyhow!("invalid --meta key: {raw}"))?;
+    let value = serde_json::from_str(value.trim()).unwrap_or_else(|_| value.trim().into());
+    Ok((key, value))
+}
+
+pub fn parse_meta_args(args: &[String]) -> Result<MetaMap> {
+    args.iter().map(|raw| parse_meta_arg(raw)).collect()
+}
+
+/// The file a `<file>.meta.json` sidecar describes, if that file exists.
+pub fn sidecar_owner(path: &Path) -> Option<PathBuf> {
+    let name = path.file_name()?.to_str()?;
+    let owner = name.strip_suffix(SIDECAR_SUFFIX)?;
+    if owner.is_empty() {
+        return None;
+    }
+    let owner = path.with_file_name(owner);
+    owner.is_file().then_some(owner)
+}
+
+/// Metadata files are merged into the docs they describe rather than indexed themselves.
+pub fn is_meta_file(path: &Path) -> bool {
+    path.file_name().is_some_and(|name| name == DIR_META_FILE) || sidecar_owner(path).is_some()
+}
+
+/// Merge `.recallmeta` files from the outermost directory of `path` inward, then the file's own
+/// sidecar. Only directories named in `path` are consulted, so `recall add docs/` never reads
+/// metadata from above the working directory.
+pub fn file_meta(path: &Path) -> Result<MetaMap> {
+    let mut meta = MetaMap::new();
+    let dirs: Vec<&Path> = path.ancestors().skip(1).collect();
+    for dir in dirs.into_iter().rev() {
+        let dir_file = if dir.as_os_str().is_empty() {
+            PathBuf::from(DIR_META_FILE)
+        } else {
+            dir.join(DIR_META_FILE)
+        };
+        if !dir_file.is_file() {
+            continue;
+        }
+        let raw = std::fs::read_to_string(&dir_file)
+            .with_context(|| format!("read {}", dir_file.display()))?;
+        let table: toml::Table =
+            toml::from_str(&raw).with_context(|| format!("parse {}", dir_file.display()))?;
+        for (key, value) in table {
+            if let Some(key) = normalize_meta_key(&key) {
+                meta.insert(key, toml_to_json(value));
+            }
+        }
+    }
+
+    let mut sidecar = path.as_os_str().to_owned();
+    sidecar.push(SIDECAR_SUFFIX);
+    let sidecar = PathBuf::from(sidecar);
+    if sidecar.is_file() {
+        let raw = std::fs::read_to_string(&sidecar)
+            .with_context(|| format!("read {}", sidecar.display()))?;
+        let value: serde_json::Value =
+            serde_json::from_str(&raw).with_context(|| format!("parse {}", sidecar.display()))?;
+        let serde_json::Value::Object(map) = value else {
+            anyhow::bail!("{} must contain a JSON object", sidecar.display());
+        };
+        for (key, value) in map {
+            if let Some(key) = normalize_meta_key(&key) {
+                meta.insert(key, value);
+            }
+        }
+    }
+    Ok(meta)
+}
+
+pub fn toml_to_json(value: toml::Value) -> serde_json::Value {
+    match value {
+        toml::Value::String(s) => s.into(),
+        toml::Value::Integer(i) => i.into(),
+        toml::Value::Float(f) => serde_json::Number::from_f64(f)
+            .map(serde_json::Value::Number)
+            .unwrap_or(serde_json::Value::Null),
+        toml::Value::Boolean(b) => b.into(),
+        toml::Value::Datetime(dt) => dt.to_string().into(),
+        toml::Value::Array(items) => items.into_iter().map(toml_to_json).collect(),
+        toml::Value::Table(table) => table
+            .into_iter()
+            .map(|(key, value)| (key, toml_to_json(value)))
+            .collect::<MetaMap>()
+            .into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn meta_args_keep_json_types() -> Result<()> {
+        assert_eq!(
+            parse_meta_arg("owner=payments")?,
+            ("owner".to_string(), "payments".into())
+        );
+        assert_eq!(parse_meta_arg("Tier=1")?, ("tier".to_string(), 1.into()));
+        assert_eq!(
+            parse_meta_arg("tags=[\"a\",\"b\"]")?.1,
+            serde_json::json!(["a", "b"])
+        );
+        assert!(parse_meta_arg("missing-equals").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn sidecar_overrides_directory_meta() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let sub = dir.path().join("sub");
+        std::fs::create_dir(&sub)?;
+        std::fs::write(
+            dir.path().join(DIR_META_FILE),
+            "owner = \"docs\"\ntier = 2\n",
+        )?;
+        std::fs::write(sub.join(DIR_META_FILE), "tier = 1\n")?;
+        let file = sub.join("report.pdf");
+        std::fs::write(&file, b"%PDF")?;
+        std::fs::write(sub.join("report.pdf.meta.json"), r#"{"owner": "payments"}"#)?;
+
+        let meta = file_meta(&file)?;
+        assert_eq!(meta.get("owner"), Some(&serde_json::json!("payments")));
+        assert_eq!(meta.get("tier"), Some(&serde_json::json!(1)));
+        assert!(is_meta_file(&sub.join("report.pdf.meta.json")));
+        assert!(is_meta_file(&sub.join(DIR_META_FILE)));
+        assert!(!is_meta_file(&file));
+        Ok(())
+    }
+}
@@ -28,6 +28,8 @@ pub struct DocRow {
     pub tag: Option<String>,
     pub source: Option<String>,
     pub meta: Option<String>,
+    pub tags: Vec<String>,
+    pub title: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -37,6 +39,47 @@ pub struct ChunkRow {
     pub offset: i64,
     pub tokens: i64,
     pub text: String,
+    pub meta: Option<String>,
+    /// Heading breadcrumb, e.g. `Deploy > Rollback > Steps`.
+    pub section: Option<String>,
+    /// Stored SimHash of `text`; `None` for chunks written before fingerprints were recorded.
+    #[serde(skip)]
+    pub simhash: Option<u64>,
+}
+
+/// Section a chunk was cut from (a Markdown heading section or a code definition).
+#[derive(Debug, Clone)]
+pub struct ParentRow {
+    pub id: String,
+    pub doc_id: String,
+    pub offset: i64,
+    pub text: String,
+}
+
+/// One `recall symbols` match: where a name is defined, imported, or called.
+#[derive(Debug, Clone, Serialize)]
+pub struct SymbolHit {
+    pub name: String,
+    pub kind: String,
+    pub doc_id: String,
+    pub path: String,
+    pub line: i64,
+    pub text: String,
+}
+
+/// A doc, or a chunk when `chunk_id` is set, in a `recall dupes` cluster.
+#[derive(Debug, Clone, Serialize)]
+pub struct DupeMember {
+    pub doc_id: String,
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chunk_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    #[serde(skip)]
+    pub simhash: u64,
 }
 
 #[derive(Debug, Clone)]
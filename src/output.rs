@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::BTreeMap;
+
 use anyhow::Result;
 use serde::Serialize;
 use serde_json::Value;
@@ -85,6 +87,13 @@ pub struct ErrorOut {
     pub hint: Option<String>,
 }
 
+/// A file that failed during ingest; the rest of the run continued.
+#[derive(Debug, Clone, Serialize)]
+pub struct FileErrorOut {
+    pub path: String,
+    pub message: String,
+}
+
 #[derive(Debug, Clone, Serialize, Default)]
 pub struct JsonResponse {
     pub ok: bool,
@@ -102,9 +111,19 @@ pub struct JsonResponse {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub diagnostics: Option<Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub plan: Option<Value>,
+    /// Per-outcome file counts and totals for `recall add`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ingest: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub actions: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub warnings: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<FileErrorOut>,
+    /// Redacted secrets per rule during `recall add`.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub redactions: BTreeMap<String, usize>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub next_offset: Option<usize>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -191,6 +210,16 @@ impl JsonResponse {
         self
     }
 
+    pub fn with_plan(mut self, plan: Value) -> Self {
+        self.plan = Some(plan);
+        self
+    }
+
+    pub fn with_ingest(mut self, ingest: Value) -> Self {
+        self.ingest = Some(ingest);
+        self
+    }
+
     pub fn with_actions(mut self, actions: Vec<String>) -> Self {
         if actions.is_empty() {
             return self;
@@ -203,6 +232,16 @@ impl JsonResponse {
         self.warnings = warnings;
         self
     }
+
+    pub fn with_errors(mut self, errors: Vec<FileErrorOut>) -> Self {
+        self.errors = errors;
+        self
+    }
+
+    pub fn with_redactions(mut self, redactions: BTreeMap<String, usize>) -> Self {
+        self.redactions = redactions;
+        self
+    }
 }
 
 pub fn print_json(resp: &JsonResponse) -> Result<()> {
@@ -0,0 +1,119 @@
+// Copyright 2026 Recall Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Portable doc paths: relative to the store root, symlinks resolved, `/`-separated.
+
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocPath {
+    /// Stored as `doc.path`: relative to the store root, or absolute when outside it.
+    pub path: String,
+    /// Absolute path at ingest time, kept as `doc.origin` for provenance.
+    pub origin: String,
+}
+
+/// Normalize `path` (absolute or relative to the working directory) against `root`, which must
+/// already be canonical. Paths that no longer exist are resolved through their nearest existing
+/// ancestor, so deleted files normalize the same way they did when they were ingested.
+pub fn normalize(root: &Path, path: &Path) -> DocPath {
+    let absolute = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        std::env::current_dir().unwrap_or_default().join(path)
+    };
+    let resolved = resolve(&lexical(&absolute));
+    let origin = to_slash(&resolved);
+    let path = match resolved.strip_prefix(root) {
+        Ok(relative) => to_slash(relative),
+        Err(_) => origin.clone(),
+    };
+    DocPath { path, origin }
+}
+
+/// Drop `.` components and fold `..` into the preceding component.
+fn lexical(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                out.pop();
+            }
+            other => out.push(other),
+        }
+    }
+    out
+}
+
+/// Canonicalize the longest existing prefix of `path` and re-append the rest.
+fn resolve(path: &Path) -> PathBuf {
+    let mut missing = Vec::new();
+    let mut current = path;
+    loop {
+        if let Ok(canonical) = current.canonicalize() {
+            return missing
+                .iter()
+                .rev()
+                .fold(canonical, |acc: PathBuf, name| acc.join(name));
+        }
+        match (current.parent(), current.file_name()) {
+            (Some(parent), Some(name)) => {
+                missing.push(name.to_os_string());
+                current = parent;
+            }
+            _ => return path.to_path_buf(),
+        }
+    }
+}
+
+fn to_slash(path: &Path) -> String {
+    let text = path.to_string_lossy();
+    if std::path::MAIN_SEPARATOR == '/' {
+        text.into_owned()
+    } else {
+        text.replace(std::path::MAIN_SEPARATOR, "/")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn equivalent_spellings_share_a_path() -> anyhow::Result<()> {
+        let temp = tempfile::tempdir()?;
+        let root = temp.path().canonicalize()?;
+        std::fs::create_dir_all(root.join("docs"))?;
+        std::fs::write(root.join("docs/a.md"), "a")?;
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(root.join("docs"), root.join("alias"))?;
+
+        let expected = normalize(&root, &root.join("docs/a.md"));
+        assert_eq!(expected.path, "docs/a.md");
+        assert_eq!(expected.origin, to_slash(&root.join("docs/a.md")));
+        assert_eq!(normalize(&root, &root.join("docs/./x/../a.md")), expected);
+        #[cfg(unix)]
+        assert_eq!(normalize(&root, &root.join("alias/a.md")), expected);
+
+        // Deleted files keep their store-relative spelling.
+        assert_eq!(
+            normalize(&root, &root.join("docs/gone.md")).path,
+            "docs/gone.md"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,328 @@
+// Copyright 2026 Recall Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! `--progress` reporting on stderr for long-running commands: JSONL events or a terminal bar.
+
+use std::cell::Cell;
+use std::cell::RefCell;
+use std::io::Write;
+use std::time::Duration;
+use std::time::Instant;
+
+use anyhow::Result;
+use indicatif::ProgressBar;
+use indicatif::ProgressStyle;
+use serde_json::Value;
+use serde_json::json;
+
+/// Import emits a JSONL event every this many lines, plus one at the end.
+const IMPORT_EVENT_LINES: u64 = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProgressMode {
+    /// One JSON object per line on stderr.
+    Jsonl,
+    /// A progress bar on stderr, drawn only when stderr is a terminal.
+    Bar,
+}
+
+impl ProgressMode {
+    pub fn parse(raw: &str) -> Result<Self> {
+        match raw.trim().to_lowercase().as_str() {
+            "jsonl" => Ok(Self::Jsonl),
+            "bar" => Ok(Self::Bar),
+            _ => anyhow::bail!("unsupported progress mode '{raw}'; supported: jsonl, bar"),
+        }
+    }
+}
+
+enum Sink {
+    Off,
+    Jsonl(RefCell<Box<dyn Write>>),
+    Bar(ProgressBar),
+}
+
+/// Progress for one command. Every method is a no-op when progress is off.
+pub struct Progress {
+    sink: Sink,
+    started: Instant,
+    /// When the total became known; ETAs extrapolate from the pace since then.
+    counting: Cell<Option<Instant>>,
+    /// Files (ingest) or bytes (import) expected, once known.
+    total: Cell<u64>,
+    done: Cell<u64>,
+    chunks: Cell<usize>,
+    lines: Cell<u64>,
+}
+
+impl Progress {
+    pub fn new(mode: Option<ProgressMode>) -> Self {
+        match mode {
+            None => Self::with_sink(Sink::Off),
+            Some(ProgressMode::Jsonl) => {
+                Self::with_sink(Sink::Jsonl(RefCell::new(Box::new(std::io::stderr()))))
+            }
+            Some(ProgressMode::Bar) => {
+                let bar = ProgressBar::new_spinner();
+                bar.enable_steady_tick(Duration::from_millis(100));
+                Self::with_sink(Sink::Bar(bar))
+            }
+        }
+    }
+
+    pub fn off() -> Self {
+        Self::new(None)
+    }
+
+    fn with_sink(sink: Sink) -> Self {
+        Self {
+            sink,
+            started: Instant::now(),
+            counting: Cell::new(None),
+            total: Cell::new(0),
+            done: Cell::new(0),
+            chunks: Cell::new(0),
+            lines: Cell::new(0),
+        }
+    }
+
+    /// A new stage of the command, such as `discover`, `ingest`, or `vacuum`.
+    pub fn phase(&self, phase: &str) {
+        self.emit("phase", json!({ "phase": phase }));
+        if let Sink::Bar(bar) = &self.sink {
+            bar.set_message(phase.to_string());
+        }
+    }
+
+    /// Files found by a walk; later file events count against the running total.
+    pub fn discovered(&self, files: usize) {
+        self.total.set(self.total.get() + files as u64);
+        self.start_counting();
+        self.emit(
+            "discovered",
+            json!({ "files": files, "total": self.total.get() }),
+        );
+        if let Sink::Bar(bar) = &self.sink {
+            bar.set_style(bar_style("{pos}/{len} files"));
+            bar.set_length(self.total.get());
+        }
+    }
+
+    pub fn file_started(&self, path: &str) {
+        self.emit(
+            "file_started",
+            json!({
+                "path": path,
+                "index": self.done.get() + 1,
+                "total": self.total.get(),
+            }),
+        );
+        if let Sink::Bar(bar) = &self.sink {
+            bar.set_message(path.to_string());
+        }
+    }
+
+    /// `docs` and `chunks` are what the file added; `ok` is false when it was rolled back.
+    pub fn file_finished(&self, path: &str, docs: usize, chunks: usize, ok: bool) {
+        self.done.set(self.done.get() + 1);
+        if chunks > 0 {
+            self.chunks.set(self.chunks.get() + chunks);
+            self.emit(
+                "chunks_embedded",
+                json!({
+                    "path": path,
+                    "chunks": chunks,
+                    "total_chunks": self.chunks.get(),
+                }),
+            );
+        }
+        self.emit(
+            "file_finished",
+            json!({
+                "path": path,
+                "index": self.done.get(),
+                "total": self.total.get(),
+                "ok": ok,
+                "docs": docs,
+                "chunks": chunks,
+                "eta_ms": self.eta_ms(),
+            }),
+        );
+        if let Sink::Bar(bar) = &self.sink {
+            bar.inc(1);
+        }
+    }
+
+    /// Size of the import input, when it is a file.
+    pub fn import_started(&self, total_bytes: Option<u64>) {
+        self.total.set(total_bytes.unwrap_or(0));
+        self.start_counting();
+        if let Sink::Bar(bar) = &self.sink
+            && let Some(total) = total_bytes
+        {
+            bar.set_style(bar_style("{bytes}/{total_bytes}"));
+            bar.set_length(total);
+        }
+    }
+
+    /// One import line consumed, with running totals.
+    pub fn import_line(&self, bytes: u64, docs: usize, chunks: usize) {
+        self.done.set(self.done.get() + bytes);
+        self.lines.set(self.lines.get() + 1);
+        if self.lines.get().is_multiple_of(IMPORT_EVENT_LINES) {
+            self.import_event(docs, chunks);
+        }
+        if let Sink::Bar(bar) = &self.sink {
+            bar.set_position(self.done.get());
+        }
+    }
+
+    pub fn import_finished(&self, docs: usize, chunks: usize) {
+        if self.lines.get() == 0 || !self.lines.get().is_multiple_of(IMPORT_EVENT_LINES) {
+            self.import_event(docs, chunks);
+        }
+    }
+
+    fn import_event(&self, docs: usize, chunks: usize) {
+        self.emit(
+            "imported",
+            json!({
+                "lines": self.lines.get(),
+                "docs": docs,
+                "chunks": chunks,
+                "bytes": self.done.get(),
+                "total_bytes": self.total.get(),
+                "eta_ms": self.eta_ms(),
+            }),
+        );
+    }
+
+    /// End of the command: a final `done` event, and the bar is cleared.
+    pub fn finish(&self) {
+        self.emit("done", json!({}));
+        if let Sink::Bar(bar) = &self.sink {
+            bar.finish_and_clear();
+        }
+    }
+
+    /// Remaining time extrapolated from the pace so far; `None` until there is a pace.
+    fn eta_ms(&self) -> Option<u64> {
+        let (done, total) = (self.done.get(), self.total.get());
+        if done == 0 || total < done {
+            return None;
+        }
+        let elapsed = self.counting.get()?.elapsed().as_millis() as u64;
+        Some(elapsed * (total - done) / done)
+    }
+
+    fn start_counting(&self) {
+        if self.counting.get().is_none() {
+            self.counting.set(Some(Instant::now()));
+        }
+    }
+
+    fn emit(&self, event: &str, fields: Value) {
+        let Sink::Jsonl(out) = &self.sink else {
+            return;
+        };
+        let mut object = serde_json::Map::new();
+        object.insert("event".to_string(), event.into());
+        if let Value::Object(fields) = fields {
+            object.extend(fields);
+        }
+        object.insert(
+            "elapsed_ms".to_string(),
+            (self.started.elapsed().as_millis() as u64).into(),
+        );
+        // Progress is best-effort; a closed stderr must not fail the command.
+        let _ = writeln!(out.borrow_mut(), "{}", Value::Object(object));
+    }
+}
+
+impl Drop for Progress {
+    fn drop(&mut self) {
+        if let Sink::Bar(bar) = &self.sink {
+            bar.finish_and_clear();
+        }
+    }
+}
+
+fn bar_style(counts: &str) -> ProgressStyle {
+    ProgressStyle::with_template(&format!(
+        "{{spinner}} [{{bar:30}}] {counts} (eta {{eta}}) {{wide_msg}}"
+    ))
+    .expect("progress template")
+    .progress_chars("=> ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Default)]
+    struct Buffer(std::rc::Rc<RefCell<Vec<u8>>>);
+
+    impl Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.borrow_mut().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn jsonl_events_track_files_and_chunks() {
+        let buffer = Buffer::default();
+        let progress = Progress::with_sink(Sink::Jsonl(RefCell::new(Box::new(buffer.clone()))));
+        progress.phase("discover");
+        progress.discovered(2);
+        progress.file_started("a.md");
+        progress.file_finished("a.md", 1, 3, true);
+        progress.file_started("b.md");
+        progress.file_finished("b.md", 0, 0, false);
+        progress.finish();
+
+        let events: Vec<Value> = String::from_utf8(buffer.0.borrow().clone())
+            .expect("utf8")
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("json line"))
+            .collect();
+        let names: Vec<&str> = events
+            .iter()
+            .map(|e| e["event"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "phase",
+                "discovered",
+                "file_started",
+                "chunks_embedded",
+                "file_finished",
+                "file_started",
+                "file_finished",
+                "done"
+            ]
+        );
+        assert_eq!(events[3]["total_chunks"], 3);
+        assert_eq!(events[4]["index"], 1);
+        assert_eq!(events[4]["total"], 2);
+        assert!(events[4]["eta_ms"].is_u64());
+        assert_eq!(events[6]["ok"], false);
+        assert_eq!(events[6]["eta_ms"], 0);
+        assert!(events.iter().all(|e| e["elapsed_ms"].is_u64()));
+    }
+}
@@ -23,10 +23,13 @@ use rusqlite::types::Value as SqlValue;
 use serde_json::json;
 
 use crate::config::Config;
+use crate::dedup;
+use crate::dedup::hamming;
 use crate::embed::Embedder;
 use crate::embed::build_embedder;
 use crate::embed::resolve_embedding;
 use crate::embed::to_bytes;
+use crate::lang;
 use crate::model::ChunkRow;
 use crate::model::DocRow;
 use crate::model::ScoredItem;
@@ -45,6 +48,7 @@ use crate::rql::Table;
 use crate::rql::Value;
 use crate::rql::parse_filter;
 use crate::rql::parse_rql;
+use crate::sql::DOC_TAGS_SQL;
 use crate::sql::SqlColumn;
 use crate::sql::SqlExpr;
 use crate::sql::SqlFragment;
@@ -64,6 +68,8 @@ pub struct SearchOptions {
     pub explain: bool,
     pub lexical_mode: LexicalMode,
     pub snapshot: Option<String>,
+    /// Keep only the best-scoring chunk of each group of near-duplicates.
+    pub collapse_dupes: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -84,6 +90,8 @@ struct LexicalRun {
     warning: Option<String>,
     original: String,
     sanitized: Option<String>,
+    /// The query actually matched, when stemming or CJK bigrams rewrote it.
+    expanded: Option<String>,
 }
 
 impl LexicalMode {
@@ -145,6 +153,14 @@ impl ScoredItem {
             if doc_fields.contains(&"source") {
                 doc.insert("source".into(), serde_json::json!(self.doc.source));
             }
+            if doc_fields.contains(&"title")
+                && let Some(title) = &self.doc.title
+            {
+                doc.insert("title".into(), serde_json::json!(title));
+            }
+            if doc_fields.contains(&"tags") && !self.doc.tags.is_empty() {
+                doc.insert("tags".into(), serde_json::json!(self.doc.tags));
+            }
             if doc_fields.contains(&"meta")
                 && let Some(meta) = &self.doc.meta
             {
@@ -176,6 +192,20 @@ impl ScoredItem {
             if chunk_fields.contains(&"text") {
                 ch.insert("text".into(), serde_json::json!(chunk.text));
             }
+            if chunk_fields.contains(&"section")
+                && let Some(section) = &chunk.section
+            {
+                ch.insert("section".into(), serde_json::json!(section));
+            }
+            if chunk_fields.contains(&"meta")
+                && let Some(meta) = &chunk.meta
+            {
+                if let Ok(value) = serde_json::from_str::<serde_json::Value>(meta) {
+                    ch.insert("meta".into(), value);
+                } else {
+                    ch.insert("meta".into(), serde_json::json!(meta));
+                }
+            }
             if !ch.is_empty() {
                 obj.insert("chunk".into(), serde_json::Value::Object(ch));
             }
@@ -254,11 +284,15 @@ fn push_unique(vec: &mut Vec<&'static str>, name: &str) {
 }
 
 fn doc_field_list() -> Vec<&'static str> {
-    vec!["id", "path", "mtime", "hash", "tag", "source", "meta"]
+    vec![
+        "id", "path", "mtime", "hash", "tag", "tags", "source", "title", "meta",
+    ]
 }
 
 fn chunk_field_list() -> Vec<&'static str> {
-    vec!["id", "doc_id", "offset", "tokens", "text"]
+    vec![
+        "id", "doc_id", "offset", "tokens", "text", "section", "meta",
+    ]
 }
 
 fn normalize_doc_field(name: &str) -> Option<&'static str> {
@@ -268,7 +302,9 @@ fn normalize_doc_field(name: &str) -> Option<&'static str> {
         "mtime" => Some("mtime"),
         "hash" => Some("hash"),
         "tag" => Some("tag"),
+        "tags" => Some("tags"),
         "source" => Some("source"),
+        "title" => Some("title"),
         "meta" => Some("meta"),
         _ => None,
     }
@@ -281,6 +317,8 @@ fn normalize_chunk_field(name: &str) -> Option<&'static str> {
         "offset" => Some("offset"),
         "tokens" => Some("tokens"),
         "text" => Some("text"),
+        "section" => Some("section"),
+        "meta" => Some("meta"),
         _ => None,
     }
 }
@@ -301,6 +339,9 @@ fn doc_field_to_column(name: &str) -> Option<SqlColumn> {
         "hash" => Some(SqlColumn::DocHash),
         "tag" => Some(SqlColumn::DocTag),
         "source" => Some(SqlColumn::DocSource),
+        "origin" => Some(SqlColumn::DocOrigin),
+        "lang" => Some(SqlColumn::DocLang),
+        "title" => Some(SqlColumn::DocTitle),
         "meta" => Some(SqlColumn::DocMeta),
         _ => None,
     }
@@ -313,6 +354,9 @@ fn chunk_field_to_column(name: &str) -> Option<SqlColumn> {
         "offset" => Some(SqlColumn::ChunkOffset),
         "tokens" => Some(SqlColumn::ChunkTokens),
         "text" => Some(SqlColumn::ChunkText),
+        "meta" => Some(SqlColumn::ChunkMeta),
+        "lang" => Some(SqlColumn::ChunkLang),
+        "section" => Some(SqlColumn::ChunkSection),
         _ => None,
     }
 }
@@ -348,6 +392,8 @@ fn select_doc_items() -> Vec<SqlSelectItem> {
         SqlSelectItem::new(SqlExpr::column(SqlColumn::DocTag)),
         SqlSelectItem::new(SqlExpr::column(SqlColumn::DocSource)),
         SqlSelectItem::new(SqlExpr::column(SqlColumn::DocMeta)),
+        SqlSelectItem::new(SqlExpr::raw(DOC_TAGS_SQL)),
+        SqlSelectItem::new(SqlExpr::column(SqlColumn::DocTitle)),
     ]
 }
 
@@ -358,6 +404,9 @@ fn select_chunk_doc_items() -> Vec<SqlSelectItem> {
         SqlSelectItem::new(SqlExpr::column(SqlColumn::ChunkOffset)),
         SqlSelectItem::new(SqlExpr::column(SqlColumn::ChunkTokens)),
         SqlSelectItem::new(SqlExpr::column(SqlColumn::ChunkText)),
+        SqlSelectItem::new(SqlExpr::column(SqlColumn::ChunkMeta)),
+        SqlSelectItem::new(SqlExpr::column(SqlColumn::ChunkSection)),
+        SqlSelectItem::new(SqlExpr::column(SqlColumn::ChunkSimhash)),
     ];
     items.extend(vec![
         SqlSelectItem::new(SqlExpr::column(SqlColumn::DocId)),
@@ -367,6 +416,8 @@ fn select_chunk_doc_items() -> Vec<SqlSelectItem> {
         SqlSelectItem::new(SqlExpr::column(SqlColumn::DocTag)),
         SqlSelectItem::new(SqlExpr::column(SqlColumn::DocSource)),
         SqlSelectItem::new(SqlExpr::column(SqlColumn::DocMeta)),
+        SqlSelectItem::new(SqlExpr::raw(DOC_TAGS_SQL)),
+        SqlSelectItem::new(SqlExpr::column(SqlColumn::DocTitle)),
     ]);
     items
 }
@@ -429,12 +480,20 @@ pub fn search_chunks_with_inputs(
         }
     }
 
+    // Collapsing drops hits, so fetch extra to still fill `k`.
+    let fetch_k = if opts.collapse_dupes {
+        opts.k.saturating_mul(4).min(config.max_limit.max(opts.k))
+    } else {
+        opts.k
+    };
+    let candidate_k = candidate_k.max(fetch_k);
+
     let mut lexical_results = Vec::new();
     let mut lexical_run: Option<LexicalRun> = None;
     if opts.use_lexical {
         let lex_start = Instant::now();
         if let Some(lex_query) = inputs.lexical.clone() {
-            let run = lexical_search(store, &lex_query, &filter, opts.k, opts.lexical_mode)?;
+            let run = lexical_search(store, &lex_query, &filter, fetch_k, opts.lexical_mode)?;
             lexical_results = run.results.clone();
             if let Some(warning) = run.warning.clone() {
                 explain_warnings.push(warning);
@@ -458,7 +517,7 @@ pub fn search_chunks_with_inputs(
         if let Some(sem_query) = inputs.semantic.clone() {
             let embedder = embedder.as_deref().expect("embedder");
             semantic_results =
-                semantic_search(store, embedder, &sem_query, &filter, opts.k, candidate_k)?;
+                semantic_search(store, embedder, &sem_query, &filter, fetch_k, candidate_k)?;
         } else {
             explain_warnings
                 .push("semantic search requested but no semantic query provided".to_string());
@@ -472,7 +531,8 @@ pub fn search_chunks_with_inputs(
         .unwrap_or(0);
     let semantic_count = semantic_results.len();
     let combine_start = Instant::now();
-    let items = combine_results(config, lexical_results, semantic_results, opts.k);
+    let collapse = opts.collapse_dupes.then_some(config.dedup_distance);
+    let items = combine_results(config, lexical_results, semantic_results, opts.k, collapse);
     timings.combine_ms = Some(combine_start.elapsed().as_millis() as i64);
 
     let snapshot = opts
@@ -604,6 +664,9 @@ fn build_explain_payload(
             } else {
                 lex.insert("sanitized_applied".into(), json!(false));
             }
+            if let Some(expanded) = &run.expanded {
+                lex.insert("expanded".into(), json!(expanded));
+            }
         }
         obj.insert("lexical".into(), serde_json::Value::Object(lex));
     }
@@ -636,6 +699,7 @@ pub fn run_rql(
             explain,
             lexical_mode,
             snapshot: snapshot.clone(),
+            collapse_dupes: false,
         };
         let mut result = search_chunks_with_inputs(
             store,
@@ -734,6 +798,8 @@ fn run_structured_query(
                 tag: row.get(4)?,
                 source: row.get(5)?,
                 meta: row.get(6)?,
+                tags: parse_tags(row.get(7)?),
+                title: row.get(8)?,
             })
         })?;
         for row in rows {
@@ -789,10 +855,14 @@ fn lexical_search(
     k: usize,
     mode: LexicalMode,
 ) -> Result<LexicalRun> {
-    let run = |query: &str| -> Result<Vec<ScoredItem>> {
+    let langs = store.chunk_languages()?;
+    let run = |query: &str| -> Result<(Vec<ScoredItem>, Option<String>)> {
+        let expanded = lang::expand_query(query, &langs);
         let fts_clause = SqlFragment::raw_with_params(
             "chunk_fts MATCH ?",
-            vec![SqlValue::from(query.to_string())],
+            vec![SqlValue::from(
+                expanded.as_deref().unwrap_or(query).to_string(),
+            )],
         );
         let where_clause = base_chunk_doc_filter().and(filter.clone()).and(fts_clause);
         let (sql, params) = SqlSelectBuilder::new(SqlTable::ChunkFts)
@@ -818,7 +888,7 @@ fn lexical_search(
         let mut stmt = store.conn.prepare(&sql)?;
         let rows = stmt.query_map(params_from_iter(params), |row| {
             let (chunk, doc) = map_chunk_row(row)?;
-            let bm25: f64 = row.get(12)?;
+            let bm25: f64 = row.get(17)?;
             let score = 1.0 / (1.0 + bm25.max(0.0));
             Ok(ScoredItem {
                 score: score as f32,
@@ -833,7 +903,7 @@ fn lexical_search(
         for row in rows {
             results.push(row?);
         }
-        Ok(results)
+        Ok((results, expanded))
     };
 
     let original = query.to_string();
@@ -856,16 +926,18 @@ fn lexical_search(
                 ),
                 original,
                 sanitized,
+                expanded: None,
             });
         }
     }
 
     match run(&query_to_run) {
-        Ok(results) => Ok(LexicalRun {
+        Ok((results, expanded)) => Ok(LexicalRun {
             results,
             warning,
             original,
             sanitized,
+            expanded,
         }),
         Err(err) => {
             if is_fts5_syntax_error(&err) && matches!(mode, LexicalMode::Fts5) {
@@ -882,14 +954,16 @@ fn lexical_search(
                         ),
                         original,
                         sanitized,
+                        expanded: None,
                     });
                 }
                 match run(&safe) {
-                    Ok(results) => Ok(LexicalRun {
+                    Ok((results, expanded)) => Ok(LexicalRun {
                         results,
                         warning: Some(format!("lexical query sanitized for FTS5: \"{}\"", safe)),
                         original,
                         sanitized,
+                        expanded,
                     }),
                     Err(_) => Err(err),
                 }
@@ -950,8 +1024,8 @@ fn semantic_search_vec(
     }
     let where_clause = base_chunk_doc_filter().and(filter.clone());
     let sql = format!(
-        "WITH knn AS (\n  SELECT chunk_rowid, distance\n  FROM chunk_vec\n  WHERE embedding MATCH ? AND k = ?\n)\nSELECT chunk.id, chunk.doc_id, chunk.offset, chunk.tokens, chunk.text,\n       doc.id, doc.path, doc.mtime, doc.hash, doc.tag, doc.source, doc.meta,\n       knn.distance\nFROM knn\nINNER JOIN chunk ON chunk.rowid = knn.chunk_rowid\nINNER JOIN doc ON doc.id = chunk.doc_id\nWHERE {}",
-        where_clause.sql
+        "WITH knn AS (\n  SELECT chunk_rowid, distance\n  FROM chunk_vec\n  WHERE embedding MATCH ? AND k = ?\n)\nSELECT chunk.id, chunk.doc_id, chunk.offset, chunk.tokens, chunk.text, chunk.meta, chunk.section, chunk.simhash,\n       doc.id, doc.path, doc.mtime, doc.hash, doc.tag, doc.source, doc.meta,\n       {}, doc.title,\n       knn.distance\nFROM knn\nINNER JOIN chunk ON chunk.rowid = knn.chunk_rowid\nINNER JOIN doc ON doc.id = chunk.doc_id\nWHERE {}",
+        DOC_TAGS_SQL, where_clause.sql
     );
     let mut params = Vec::new();
     params.push(SqlValue::from(to_bytes(query_vec)));
@@ -960,7 +1034,7 @@ fn semantic_search_vec(
 
     let mut stmt = store.conn.prepare(&sql)?;
     let rows = stmt.query_map(params_from_iter(params), |row| {
-        let distance: f32 = row.get(12)?;
+        let distance: f32 = row.get(17)?;
         let (chunk, doc) = map_chunk_row(row)?;
         Ok((chunk, doc, distance))
     })?;
@@ -988,6 +1062,7 @@ fn combine_results(
     lexical: Vec<ScoredItem>,
     semantic: Vec<ScoredItem>,
     k: usize,
+    collapse: Option<u32>,
 ) -> Vec<ScoredItem> {
     let mut items = if lexical.is_empty() && semantic.is_empty() {
         Vec::new()
@@ -1022,6 +1097,24 @@ fn combine_results(
     };
 
     sort_by_score_with_tiebreak(&mut items, false);
+    if let Some(distance) = collapse {
+        let mut kept: Vec<u64> = Vec::new();
+        items.retain(|item| {
+            // Doc-level hits have no chunk text to compare and always stay.
+            let Some(chunk) = &item.chunk else {
+                return true;
+            };
+            // Chunks without words have no fingerprint and stay too.
+            let Some(hash) = chunk.simhash.or_else(|| dedup::simhash(&chunk.text)) else {
+                return true;
+            };
+            let duplicate = kept.iter().any(|other| hamming(hash, *other) <= distance);
+            if !duplicate {
+                kept.push(hash);
+            }
+            !duplicate
+        });
+    }
     items.truncate(k);
     items
 }
@@ -1061,19 +1154,68 @@ fn map_chunk_row(row: &Row) -> rusqlite::Result<(ChunkRow, DocRow)> {
         offset: row.get(2)?,
         tokens: row.get(3)?,
         text: row.get(4)?,
+        meta: row.get(5)?,
+        section: row.get(6)?,
+        simhash: row.get::<_, Option<i64>>(7)?.map(|hash| hash as u64),
     };
     let doc = DocRow {
-        id: row.get(5)?,
-        path: row.get(6)?,
-        mtime: row.get(7)?,
-        hash: row.get(8)?,
-        tag: row.get(9)?,
-        source: row.get(10)?,
-        meta: row.get(11)?,
+        id: row.get(8)?,
+        path: row.get(9)?,
+        mtime: row.get(10)?,
+        hash: row.get(11)?,
+        tag: row.get(12)?,
+        source: row.get(13)?,
+        meta: row.get(14)?,
+        tags: parse_tags(row.get(15)?),
+        title: row.get(16)?,
     };
     Ok((chunk, doc))
 }
 
+fn parse_tags(raw: Option<String>) -> Vec<String> {
+    raw.and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+/// Live chunks of one doc in offset order, unscored.
+pub fn doc_chunks(store: &Store, doc_id: &str) -> Result<Vec<ScoredItem>> {
+    let sql = format!(
+        "SELECT chunk.id, chunk.doc_id, chunk.offset, chunk.tokens, chunk.text, chunk.meta, chunk.section, chunk.simhash,\n       doc.id, doc.path, doc.mtime, doc.hash, doc.tag, doc.source, doc.meta,\n       {DOC_TAGS_SQL}, doc.title\nFROM chunk\nINNER JOIN doc ON doc.id = chunk.doc_id\nWHERE doc.id = ?1 AND doc.deleted = 0 AND chunk.deleted = 0\nORDER BY chunk.offset"
+    );
+    let mut stmt = store.conn.prepare(&sql)?;
+    let rows = stmt.query_map([doc_id], map_chunk_row)?;
+    let mut items = Vec::new();
+    for row in rows {
+        let (chunk, doc) = row?;
+        items.push(ScoredItem {
+            score: 0.0,
+            lexical: None,
+            semantic: None,
+            doc,
+            chunk: Some(chunk),
+        });
+    }
+    Ok(items)
+}
+
+/// Live doc IDs matching a filter over doc fields, ordered by path.
+pub fn filter_doc_ids(store: &Store, filter: &str) -> Result<Vec<String>> {
+    let expr = parse_filter(filter)?;
+    let where_clause = base_doc_filter().and(filter_to_sql(&expr)?);
+    let (sql, params) = SqlSelectBuilder::new(SqlTable::Doc)
+        .select(vec![SqlSelectItem::new(SqlExpr::column(SqlColumn::DocId))])
+        .where_clause(where_clause)
+        .order_by(SqlOrderBy::asc(SqlExpr::column(SqlColumn::DocPath)))
+        .build();
+    let mut stmt = store.conn.prepare(&sql)?;
+    let rows = stmt.query_map(params_from_iter(params), |row| row.get::<_, String>(0))?;
+    let mut ids = Vec::new();
+    for id in rows {
+        ids.push(id?);
+    }
+    Ok(ids)
+}
+
 fn filter_to_sql(expr: &FilterExpr) -> Result<SqlFragment> {
     match expr {
         FilterExpr::And(a, b) => Ok(filter_to_sql(a)?.and(filter_to_sql(b)?)),
@@ -1085,6 +1227,46 @@ fn filter_to_sql(expr: &FilterExpr) -> Result<SqlFragment> {
 
 fn predicate_to_sql(pred: &Predicate) -> Result<SqlFragment> {
     match pred {
+        Predicate::Contains { field, value } if is_doc_tags(field) => {
+            SqlFragment::doc_has_tag(vec![value_to_sql(value)])
+        }
+        Predicate::In { field, values } if is_doc_tags(field) => {
+            SqlFragment::doc_has_tag(values.iter().map(value_to_sql).collect())
+        }
+        Predicate::Cmp { field, .. } if is_doc_tags(field) => {
+            anyhow::bail!("doc.tags supports only CONTAINS and IN")
+        }
+        Predicate::Cmp {
+            field,
+            op: CmpOp::Eq,
+            value,
+        } if is_doc_links(field) => {
+            SqlFragment::doc_links(field.name == "linked_from", vec![value_to_sql(value)])
+        }
+        Predicate::Contains { field, value } if is_doc_links(field) => {
+            SqlFragment::doc_links(field.name == "linked_from", vec![value_to_sql(value)])
+        }
+        Predicate::In { field, values } if is_doc_links(field) => SqlFragment::doc_links(
+            field.name == "linked_from",
+            values.iter().map(value_to_sql).collect(),
+        ),
+        Predicate::Cmp { field, .. } if is_doc_links(field) => {
+            anyhow::bail!("doc.{} supports only =, CONTAINS, and IN", field.name)
+        }
+        Predicate::Cmp {
+            field,
+            op: CmpOp::Eq,
+            value,
+        } if is_chunk_symbol(field) => SqlFragment::chunk_has_symbol(vec![value_to_sql(value)]),
+        Predicate::Contains { field, value } if is_chunk_symbol(field) => {
+            SqlFragment::chunk_has_symbol(vec![value_to_sql(value)])
+        }
+        Predicate::In { field, values } if is_chunk_symbol(field) => {
+            SqlFragment::chunk_has_symbol(values.iter().map(value_to_sql).collect())
+        }
+        Predicate::Cmp { field, .. } if is_chunk_symbol(field) => {
+            anyhow::bail!("chunk.symbol supports only =, CONTAINS, and IN")
+        }
         Predicate::Cmp { field, op, value } => {
             let expr = field_to_expr(field)?;
             let op_str = cmp_op_to_sql(op);
@@ -1095,9 +1277,29 @@ fn predicate_to_sql(pred: &Predicate) -> Result<SqlFragment> {
             let params = values.iter().map(value_to_sql).collect();
             SqlFragment::in_list(expr, params)
         }
+        Predicate::Contains { field, value } => match field_to_expr(field)? {
+            SqlExpr::JsonExtract { column, key } => Ok(SqlFragment::json_contains(
+                column,
+                &key,
+                value_to_sql(value),
+            )),
+            _ => anyhow::bail!("CONTAINS requires a meta field: {}", field.name),
+        },
     }
 }
 
+fn is_doc_tags(field: &FieldRef) -> bool {
+    field.table == Some(Table::Doc) && field.name == "tags"
+}
+
+fn is_doc_links(field: &FieldRef) -> bool {
+    field.table == Some(Table::Doc) && matches!(field.name.as_str(), "links_to" | "linked_from")
+}
+
+fn is_chunk_symbol(field: &FieldRef) -> bool {
+    field.table == Some(Table::Chunk) && field.name == "symbol"
+}
+
 fn value_to_sql(value: &Value) -> SqlValue {
     match value {
         Value::String(s) => SqlValue::from(s.clone()),
@@ -1128,17 +1330,8 @@ fn field_to_expr(field: &FieldRef) -> Result<SqlExpr> {
     match table {
         Table::Doc => {
             if let Some(key) = name.strip_prefix("meta.") {
-                let key = key.trim();
-                if key.is_empty() {
-                    anyhow::bail!("metadata key required after doc.meta");
-                }
-                if !key
-                    .chars()
-                    .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
-                {
-                    anyhow::bail!("metadata key contains unsupported characters: {key}");
-                }
-                return Ok(SqlExpr::json_extract(SqlColumn::DocMeta, key.to_string()));
+                let key = validate_meta_key(key, "doc.meta")?;
+                return Ok(SqlExpr::json_extract(SqlColumn::DocMeta, key));
             }
             if let Some(column) = doc_field_to_column(name) {
                 return Ok(SqlExpr::column(column));
@@ -1146,6 +1339,10 @@ fn field_to_expr(field: &FieldRef) -> Result<SqlExpr> {
             anyhow::bail!("unknown doc field: {name}")
         }
         Table::Chunk => {
+            if let Some(key) = name.strip_prefix("meta.") {
+                let key = validate_meta_key(key, "chunk.meta")?;
+                return Ok(SqlExpr::json_extract(SqlColumn::ChunkMeta, key));
+            }
             if let Some(column) = chunk_field_to_column(name) {
                 return Ok(SqlExpr::column(column));
             }
@@ -1154,6 +1351,21 @@ fn field_to_expr(field: &FieldRef) -> Result<SqlExpr> {
     }
 }
 
+fn validate_meta_key(key: &str, prefix: &str) -> Result<String> {
+    let key = key.trim();
+    if key.is_empty() {
+        anyhow::bail!("metadata key required after {prefix}");
+    }
+    if !key
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-' || c == '.')
+        || key.split('.').any(str::is_empty)
+    {
+        anyhow::bail!("metadata key contains unsupported characters: {key}");
+    }
+    Ok(key.to_string())
+}
+
 fn field_to_expr_for_table(field: &FieldRef, table: Table) -> Result<SqlExpr> {
     let table = field.table.clone().unwrap_or(table);
     let resolved = FieldRef {
@@ -1310,18 +1522,24 @@ fn field_value(item: &ScoredItem, field: &FieldRef) -> FieldValue {
                 "hash" => FieldValue::Str(item.doc.hash.clone()),
                 "tag" => FieldValue::Str(item.doc.tag.clone().unwrap_or_default()),
                 "source" => FieldValue::Str(item.doc.source.clone().unwrap_or_default()),
+                "title" => FieldValue::Str(item.doc.title.clone().unwrap_or_default()),
                 "meta" => FieldValue::Str(item.doc.meta.clone().unwrap_or_default()),
                 _ => FieldValue::None,
             }
         }
         Table::Chunk => {
             if let Some(chunk) = &item.chunk {
+                if let Some(key) = field.name.strip_prefix("meta.") {
+                    return meta_field_value(&chunk.meta, key);
+                }
                 match field.name.as_str() {
                     "id" => FieldValue::Str(chunk.id.clone()),
                     "doc_id" => FieldValue::Str(chunk.doc_id.clone()),
                     "offset" => FieldValue::Num(chunk.offset),
                     "tokens" => FieldValue::Num(chunk.tokens),
                     "text" => FieldValue::Str(chunk.text.clone()),
+                    "section" => FieldValue::Str(chunk.section.clone().unwrap_or_default()),
+                    "meta" => FieldValue::Str(chunk.meta.clone().unwrap_or_default()),
                     _ => FieldValue::None,
                 }
             } else {
@@ -1342,4 +1560,79 @@ mod tests {
         assert!(fragment.sql.contains("json_extract(doc.meta"));
         assert_eq!(fragment.params.len(), 1);
     }
+
+    #[test]
+    fn filter_allows_chunk_meta_key() {
+        let expr = parse_filter("chunk.meta.cell_type = 'code'").expect("parse filter");
+        let fragment = filter_to_sql(&expr).expect("filter to sql");
+        assert!(
+            fragment
+                .sql
+                .contains("json_extract(chunk.meta, '$.cell_type')")
+        );
+    }
+
+    #[test]
+    fn filter_doc_tags_membership() {
+        let expr =
+            parse_filter("'api' IN doc.tags OR doc.tags IN ('a', 'b')").expect("parse filter");
+        let fragment = filter_to_sql(&expr).expect("filter to sql");
+        assert_eq!(fragment.sql.matches("FROM doc_tag").count(), 2);
+        assert_eq!(fragment.params.len(), 3);
+        let expr = parse_filter("doc.tags = 'api'").expect("parse filter");
+        assert!(filter_to_sql(&expr).is_err());
+    }
+
+    #[test]
+    fn filter_doc_links_accepts_equality() {
+        let eq = parse_filter("doc.links_to = 'docs/a.md'").expect("parse filter");
+        let contains = parse_filter("doc.links_to CONTAINS 'docs/a.md'").expect("parse filter");
+        let eq = filter_to_sql(&eq).expect("filter to sql");
+        assert_eq!(eq.sql, filter_to_sql(&contains).expect("filter to sql").sql);
+        let expr = parse_filter("doc.linked_from != 'docs/a.md'").expect("parse filter");
+        assert!(filter_to_sql(&expr).is_err());
+    }
+
+    fn scored(path: &str, score: f32, chunk: Option<(&str, u64)>) -> ScoredItem {
+        ScoredItem {
+            score,
+            lexical: Some(score),
+            semantic: None,
+            doc: DocRow {
+                id: path.to_string(),
+                path: path.to_string(),
+                mtime: String::new(),
+                hash: String::new(),
+                tag: None,
+                source: None,
+                meta: None,
+                tags: Vec::new(),
+                title: None,
+            },
+            chunk: chunk.map(|(text, simhash)| ChunkRow {
+                id: format!("{path}:0"),
+                doc_id: path.to_string(),
+                offset: 0,
+                tokens: 1,
+                text: text.to_string(),
+                meta: None,
+                section: None,
+                simhash: Some(simhash),
+            }),
+        }
+    }
+
+    #[test]
+    fn collapse_uses_stored_fingerprints_and_keeps_chunkless_items() {
+        let config = Config::default();
+        let items = vec![
+            scored("a.md", 0.9, Some(("alpha", 0b0000))),
+            scored("b.md", 0.8, Some(("entirely different text", 0b0001))),
+            scored("c.md", 0.7, None),
+            scored("d.md", 0.6, Some(("alpha", 0xffff_0000))),
+        ];
+        let kept = combine_results(&config, items, Vec::new(), 10, Some(2));
+        let paths: Vec<&str> = kept.iter().map(|item| item.doc.path.as_str()).collect();
+        assert_eq!(paths, vec!["a.md", "c.md", "d.md"]);
+    }
 }
@@ -0,0 +1,219 @@
+// Copyright 2026 Recall Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Record-oriented ingestion: JSONL, CSV, and JSON array files where each
+//! record becomes its own document.
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use anyhow::Context;
+use anyhow::Result;
+use serde_json::Map;
+use serde_json::Value;
+
+#[derive(Debug, Clone)]
+pub struct RecordOptions {
+    pub text_field: String,
+    pub id_field: Option<String>,
+    pub meta_fields: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordFormat {
+    JsonLines,
+    Csv,
+    JsonArray,
+}
+
+#[derive(Debug, Clone)]
+pub struct Record {
+    pub id: String,
+    pub text: String,
+    pub meta: Map<String, Value>,
+}
+
+#[derive(Debug, Default)]
+pub struct ParsedRecords {
+    pub records: Vec<Record>,
+    pub warnings: Vec<String>,
+}
+
+pub fn record_format(path: &Path) -> Option<RecordFormat> {
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+    match ext.as_str() {
+        "jsonl" | "ndjson" => Some(RecordFormat::JsonLines),
+        "csv" => Some(RecordFormat::Csv),
+        "json" => Some(RecordFormat::JsonArray),
+        _ => None,
+    }
+}
+
+pub fn parse_records(
+    format: RecordFormat,
+    text: &str,
+    opts: &RecordOptions,
+) -> Result<ParsedRecords> {
+    let rows = match format {
+        RecordFormat::JsonLines => json_lines_rows(text)?,
+        RecordFormat::Csv => csv_rows(text)?,
+        RecordFormat::JsonArray => json_array_rows(text)?,
+    };
+
+    let mut parsed = ParsedRecords::default();
+    let mut seen = HashSet::new();
+    for (index, row) in rows.into_iter().enumerate() {
+        let number = index + 1;
+        let Some(text) = row.get(&opts.text_field).and_then(field_text) else {
+            parsed.warnings.push(format!(
+                "record {number}: missing text field '{}'",
+                opts.text_field
+            ));
+            continue;
+        };
+        let id = match &opts.id_field {
+            Some(field) => match row.get(field).and_then(field_text) {
+                Some(id) if !id.trim().is_empty() => id.trim().to_string(),
+                _ => {
+                    parsed
+                        .warnings
+                        .push(format!("record {number}: missing id field '{field}'"));
+                    continue;
+                }
+            },
+            None => number.to_string(),
+        };
+        if !seen.insert(id.clone()) {
+            parsed
+                .warnings
+                .push(format!("record {number}: duplicate id '{id}'"));
+            continue;
+        }
+
+        let mut meta = Map::new();
+        for field in &opts.meta_fields {
+            let Some(value) = row.get(field) else {
+                continue;
+            };
+            if value.is_null() {
+                continue;
+            }
+            if let Some(key) = crate::ingest::normalize_meta_key(field) {
+                meta.insert(key, value.clone());
+            }
+        }
+
+        parsed.records.push(Record { id, text, meta });
+    }
+    Ok(parsed)
+}
+
+fn field_text(value: &Value) -> Option<String> {
+    match value {
+        Value::Null => None,
+        Value::String(s) => Some(s.clone()),
+        other => Some(other.to_string()),
+    }
+}
+
+fn json_lines_rows(text: &str) -> Result<Vec<Map<String, Value>>> {
+    let mut rows = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        let value: Value = serde_json::from_str(trimmed)
+            .with_context(|| format!("parse JSONL line {}", index + 1))?;
+        match value {
+            Value::Object(map) => rows.push(map),
+            _ => anyhow::bail!("JSONL line {} is not an object", index + 1),
+        }
+    }
+    Ok(rows)
+}
+
+fn json_array_rows(text: &str) -> Result<Vec<Map<String, Value>>> {
+    let value: Value = serde_json::from_str(text).context("parse JSON records")?;
+    let Value::Array(items) = value else {
+        anyhow::bail!("JSON records file must contain a top-level array");
+    };
+    let mut rows = Vec::new();
+    for (index, item) in items.into_iter().enumerate() {
+        match item {
+            Value::Object(map) => rows.push(map),
+            _ => anyhow::bail!("JSON record {} is not an object", index + 1),
+        }
+    }
+    Ok(rows)
+}
+
+fn csv_rows(text: &str) -> Result<Vec<Map<String, Value>>> {
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .from_reader(text.as_bytes());
+    let headers = reader.headers().context("read CSV header")?.clone();
+    let mut rows = Vec::new();
+    for (index, record) in reader.records().enumerate() {
+        let record = record.with_context(|| format!("parse CSV row {}", index + 1))?;
+        let mut map = Map::new();
+        for (header, field) in headers.iter().zip(record.iter()) {
+            map.insert(header.to_string(), Value::String(field.to_string()));
+        }
+        rows.push(map);
+    }
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn opts() -> RecordOptions {
+        RecordOptions {
+            text_field: "body".to_string(),
+            id_field: Some("id".to_string()),
+            meta_fields: vec!["priority".to_string(), "Team Name".to_string()],
+        }
+    }
+
+    #[test]
+    fn json_lines_keep_typed_meta() -> Result<()> {
+        let text = "{\"id\": 7, \"body\": \"reset password\", \"priority\": \"p1\"}\n\n{\"id\": 8, \"priority\": 2}\n";
+        let parsed = parse_records(RecordFormat::JsonLines, text, &opts())?;
+        assert_eq!(parsed.records.len(), 1);
+        assert_eq!(parsed.records[0].id, "7");
+        assert_eq!(
+            parsed.records[0].meta.get("priority"),
+            Some(&Value::from("p1"))
+        );
+        assert_eq!(parsed.warnings.len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn csv_rows_map_headers() -> Result<()> {
+        let text = "id,body,priority,Team Name\nA-1,\"login fails, again\",p2,payments\n";
+        let parsed = parse_records(RecordFormat::Csv, text, &opts())?;
+        let record = &parsed.records[0];
+        assert_eq!(record.id, "A-1");
+        assert_eq!(record.text, "login fails, again");
+        assert_eq!(record.meta.get("team_name"), Some(&Value::from("payments")));
+        Ok(())
+    }
+}
@@ -98,6 +98,11 @@ pub enum Predicate {
         field: FieldRef,
         values: Vec<Value>,
     },
+    /// List membership: `field CONTAINS value` or `value IN field`.
+    Contains {
+        field: FieldRef,
+        value: Value,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -155,6 +160,7 @@ enum Keyword {
     Or,
     Not,
     In,
+    Contains,
     Like,
     Glob,
 }
@@ -393,8 +399,21 @@ impl Parser {
             self.expect(Token::RParen)?;
             return Ok(expr);
         }
+        if matches!(self.peek_token(), Some(Token::String(_) | Token::Number(_))) {
+            let value = self.expect_value()?;
+            if !self.peek_keyword(Keyword::In) {
+                anyhow::bail!("expected IN after value");
+            }
+            self.next();
+            let field = FieldRef::parse(&self.expect_ident()?);
+            return Ok(FilterExpr::Predicate(Predicate::Contains { field, value }));
+        }
         let field = FieldRef::parse(&self.expect_ident()?);
-        if self.peek_keyword(Keyword::In) {
+        if self.peek_keyword(Keyword::Contains) {
+            self.next();
+            let value = self.expect_value()?;
+            Ok(FilterExpr::Predicate(Predicate::Contains { field, value }))
+        } else if self.peek_keyword(Keyword::In) {
             self.next();
             self.expect(Token::LParen)?;
             let mut values = Vec::new();
@@ -656,6 +675,7 @@ fn lex(input: &str) -> Result<Vec<Token>> {
                 "or" => Some(Keyword::Or),
                 "not" => Some(Keyword::Not),
                 "in" => Some(Keyword::In),
+                "contains" => Some(Keyword::Contains),
                 "like" => Some(Keyword::Like),
                 "glob" => Some(Keyword::Glob),
                 _ => None,
@@ -701,4 +721,18 @@ mod tests {
             _ => panic!("expected and"),
         }
     }
+
+    #[test]
+    fn parse_membership() {
+        let expected = FilterExpr::Predicate(Predicate::Contains {
+            field: FieldRef::parse("doc.meta.tags"),
+            value: Value::String("net".to_string()),
+        });
+        assert_eq!(
+            parse_filter("doc.meta.tags CONTAINS 'net'").unwrap(),
+            expected
+        );
+        assert_eq!(parse_filter("'net' IN doc.meta.tags").unwrap(), expected);
+        assert!(parse_filter("'net' = doc.meta.tags").is_err());
+    }
 }
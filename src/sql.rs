@@ -41,6 +41,9 @@ pub enum SqlColumn {
     DocHash,
     DocTag,
     DocSource,
+    DocOrigin,
+    DocLang,
+    DocTitle,
     DocMeta,
     DocDeleted,
     ChunkRowid,
@@ -49,6 +52,10 @@ pub enum SqlColumn {
     ChunkOffset,
     ChunkTokens,
     ChunkText,
+    ChunkMeta,
+    ChunkLang,
+    ChunkSection,
+    ChunkSimhash,
     ChunkDeleted,
     ChunkFtsRowid,
 }
@@ -62,6 +69,9 @@ impl SqlColumn {
             SqlColumn::DocHash => "doc.hash",
             SqlColumn::DocTag => "doc.tag",
             SqlColumn::DocSource => "doc.source",
+            SqlColumn::DocOrigin => "doc.origin",
+            SqlColumn::DocLang => "doc.lang",
+            SqlColumn::DocTitle => "doc.title",
             SqlColumn::DocMeta => "doc.meta",
             SqlColumn::DocDeleted => "doc.deleted",
             SqlColumn::ChunkRowid => "chunk.rowid",
@@ -70,12 +80,27 @@ impl SqlColumn {
             SqlColumn::ChunkOffset => "chunk.offset",
             SqlColumn::ChunkTokens => "chunk.tokens",
             SqlColumn::ChunkText => "chunk.text",
+            SqlColumn::ChunkMeta => "chunk.meta",
+            SqlColumn::ChunkLang => "chunk.lang",
+            SqlColumn::ChunkSection => "chunk.section",
+            SqlColumn::ChunkSimhash => "chunk.simhash",
             SqlColumn::ChunkDeleted => "chunk.deleted",
             SqlColumn::ChunkFtsRowid => "chunk_fts.rowid",
         }
     }
 }
 
+/// Sorted JSON array of a doc's tags, for select lists.
+pub const DOC_TAGS_SQL: &str = "(SELECT json_group_array(tag) FROM (SELECT tag FROM doc_tag WHERE doc_tag.doc_id = doc.id ORDER BY tag))";
+
+/// True when `link` points at the doc aliased as `doc`: exact path for relative links, path
+/// suffix for wiki links.
+pub fn link_resolves_to(doc: &str) -> String {
+    format!(
+        "({doc}.path = link.target OR (link.kind = 'wiki' AND substr({doc}.path, -length(link.target) - 1) = '/' || link.target))"
+    )
+}
+
 #[derive(Clone, Debug)]
 pub enum SqlExpr {
     Column(SqlColumn),
@@ -219,6 +244,69 @@ impl SqlFragment {
         })
     }
 
+    /// Matches docs carrying any of `tags` in the doc_tag table.
+    pub fn doc_has_tag(tags: Vec<SqlValue>) -> Result<Self> {
+        if tags.is_empty() {
+            bail!("IN list cannot be empty");
+        }
+        let placeholders = vec!["?"; tags.len()].join(", ");
+        let sql = format!(
+            "EXISTS (SELECT 1 FROM doc_tag WHERE doc_tag.doc_id = doc.id AND doc_tag.tag IN ({placeholders}))"
+        );
+        Ok(Self { sql, params: tags })
+    }
+
+    /// Matches chunks that define, import, or call any of `names`.
+    pub fn chunk_has_symbol(names: Vec<SqlValue>) -> Result<Self> {
+        if names.is_empty() {
+            bail!("IN list cannot be empty");
+        }
+        let placeholders = vec!["?"; names.len()].join(", ");
+        let sql = format!(
+            "EXISTS (SELECT 1 FROM symbol WHERE symbol.chunk_id = chunk.id AND symbol.name IN ({placeholders}))"
+        );
+        Ok(Self { sql, params: names })
+    }
+
+    /// Matches docs linking to (`incoming = false`) or linked from (`incoming = true`) a live doc
+    /// whose path or id is in `targets`.
+    pub fn doc_links(incoming: bool, targets: Vec<SqlValue>) -> Result<Self> {
+        if targets.is_empty() {
+            bail!("IN list cannot be empty");
+        }
+        let placeholders = vec!["?"; targets.len()].join(", ");
+        let (join, filter) = if incoming {
+            (
+                "other.id = link.source_id".to_string(),
+                link_resolves_to("doc"),
+            )
+        } else {
+            (
+                link_resolves_to("other"),
+                "link.source_id = doc.id".to_string(),
+            )
+        };
+        let sql = format!(
+            "EXISTS (SELECT 1 FROM link JOIN doc AS other ON {join} WHERE {filter} AND other.deleted = 0 AND (other.path IN ({placeholders}) OR other.id IN ({placeholders})))"
+        );
+        let mut params = targets.clone();
+        params.extend(targets);
+        Ok(Self { sql, params })
+    }
+
+    /// Matches when the JSON value at `key` equals `value` or is an array containing it.
+    pub fn json_contains(column: SqlColumn, key: &str, value: SqlValue) -> Self {
+        let sql = format!(
+            "EXISTS (SELECT 1 FROM json_each({}, '$.{}') WHERE json_each.value = ?)",
+            column.sql(),
+            key
+        );
+        Self {
+            sql,
+            params: vec![value],
+        }
+    }
+
     pub fn and(self, other: SqlFragment) -> SqlFragment {
         let sql = format!("({}) AND ({})", self.sql, other.sql);
         let mut params = self.params;
@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::cell::RefCell;
+use std::collections::BTreeSet;
 use std::fs;
 use std::fs::File;
 use std::fs::OpenOptions;
@@ -36,15 +38,37 @@ use sha2::Sha256;
 use sqlite_vec::sqlite3_vec_init;
 
 use crate::config::Config;
+use crate::dedup::SimhashIndex;
+use crate::dedup::simhash;
 use crate::embed::EMBEDDING_HASH;
+use crate::embed::EmbedTemplate;
 use crate::embed::EmbeddingSpec;
 use crate::embed::resolve_embedding;
+use crate::lang;
+use crate::links::Link;
+use crate::model::DupeMember;
+use crate::model::ParentRow;
+use crate::model::SymbolHit;
 use crate::output::CorpusStats;
+use crate::paths::DocPath;
+use crate::progress::Progress;
+use crate::sql::link_resolves_to;
+use crate::symbols::Symbol;
+use crate::tokenize::TOKENIZER_WHITESPACE;
+use crate::tokenize::resolve_tokenizer;
 
 pub struct Store {
     pub conn: Connection,
     pub path: PathBuf,
+    /// Canonical directory holding the database; doc paths are relative to it.
+    pub root: PathBuf,
     lock: Option<StoreLock>,
+    /// Live doc fingerprints for near-duplicate lookups, loaded on first use and kept in step
+    /// with the docs this handle writes.
+    simhashes: RefCell<Option<SimhashIndex>>,
+    /// Languages of live chunks, loaded on first lexical query and extended by this handle's
+    /// writes. A language whose last chunk was deleted lingers, which only costs extra terms.
+    languages: RefCell<Option<BTreeSet<String>>>,
 }
 
 struct StoreLock {
@@ -77,10 +101,51 @@ fn register_sqlite_vec() {
     });
 }
 
-const SCHEMA_VERSION: i64 = 2;
+const SCHEMA_VERSION: i64 = 12;
+const MIN_MIGRATABLE_VERSION: i64 = 2;
 const VEC_VERSION: &str = "vec0-v1";
-const FTS_VERSION: &str = "fts5-v1";
+const FTS_VERSION: &str = "fts5-v2";
+/// Source lines stored with symbols are cut to this many characters.
+const SYMBOL_TEXT_CHARS: usize = 200;
 
+/// Additive migrations, keyed by the schema version each one produces.
+const MIGRATIONS: &[(i64, &str)] = &[
+    (3, "ALTER TABLE chunk ADD COLUMN meta TEXT;"),
+    (
+        4,
+        "CREATE TABLE IF NOT EXISTS doc_tag (\n  doc_id TEXT NOT NULL,\n  tag TEXT NOT NULL,\n  PRIMARY KEY (doc_id, tag)\n);\nCREATE INDEX IF NOT EXISTS idx_doc_tag_tag ON doc_tag(tag);\nINSERT OR IGNORE INTO doc_tag (doc_id, tag) SELECT id, tag FROM doc WHERE tag IS NOT NULL;",
+    ),
+    (5, "ALTER TABLE doc ADD COLUMN chunking TEXT;"),
+    (
+        6,
+        "ALTER TABLE chunk ADD COLUMN parent_id TEXT;\nCREATE TABLE IF NOT EXISTS chunk_parent (\n  id TEXT PRIMARY KEY,\n  doc_id TEXT,\n  offset INTEGER,\n  tokens INTEGER,\n  text TEXT\n);\nCREATE INDEX IF NOT EXISTS idx_chunk_parent_doc ON chunk_parent(doc_id);",
+    ),
+    (
+        7,
+        "CREATE TABLE IF NOT EXISTS link (\n  source_id TEXT NOT NULL,\n  target TEXT NOT NULL,\n  anchor TEXT,\n  kind TEXT NOT NULL\n);\nCREATE INDEX IF NOT EXISTS idx_link_source ON link(source_id);\nCREATE INDEX IF NOT EXISTS idx_link_target ON link(target);",
+    ),
+    (
+        8,
+        "CREATE TABLE IF NOT EXISTS symbol (\n  doc_id TEXT NOT NULL,\n  chunk_id TEXT,\n  name TEXT NOT NULL,\n  kind TEXT NOT NULL,\n  line INTEGER NOT NULL,\n  text TEXT\n);\nCREATE INDEX IF NOT EXISTS idx_symbol_name ON symbol(name);\nCREATE INDEX IF NOT EXISTS idx_symbol_doc ON symbol(doc_id);\nCREATE INDEX IF NOT EXISTS idx_symbol_chunk ON symbol(chunk_id);",
+    ),
+    (
+        9,
+        "ALTER TABLE doc ADD COLUMN origin TEXT;\nUPDATE doc SET path = substr(path, 3) WHERE path LIKE './%';\nUPDATE link SET target = substr(target, 3) WHERE target LIKE './%';",
+    ),
+    (
+        10,
+        "ALTER TABLE doc ADD COLUMN simhash INTEGER;\nALTER TABLE chunk ADD COLUMN simhash INTEGER;",
+    ),
+    // The FTS table gains a `terms` column, so it is rebuilt; triggers are recreated on open.
+    (
+        11,
+        "ALTER TABLE doc ADD COLUMN lang TEXT;\nALTER TABLE chunk ADD COLUMN lang TEXT;\nALTER TABLE chunk ADD COLUMN terms TEXT;\nCREATE INDEX IF NOT EXISTS idx_chunk_lang ON chunk(lang);\nDROP TRIGGER IF EXISTS chunk_ai;\nDROP TRIGGER IF EXISTS chunk_ad;\nDROP TRIGGER IF EXISTS chunk_au;\nDROP TABLE IF EXISTS chunk_fts;\nCREATE VIRTUAL TABLE chunk_fts USING fts5(text, terms, content='chunk', content_rowid='rowid');\nINSERT INTO chunk_fts(chunk_fts) VALUES('rebuild');\nUPDATE meta SET value = 'fts5-v2' WHERE key = 'fts_version';",
+    ),
+    (
+        12,
+        "ALTER TABLE doc ADD COLUMN title TEXT;\nALTER TABLE chunk ADD COLUMN section TEXT;",
+    ),
+];
 #[derive(Debug, Clone, Copy)]
 pub enum StoreMode {
     ReadOnly,
@@ -134,6 +199,10 @@ impl Store {
         Self::set_meta(&conn, "vec_version", VEC_VERSION)?;
         Self::set_meta(&conn, "embedding", embedding.name)?;
         Self::set_meta(&conn, "embedding_dim", &embedding_dim.to_string())?;
+        Self::set_meta(&conn, "tokenizer", resolve_tokenizer(config)?)?;
+        if let Some(template) = EmbedTemplate::from_config(config)? {
+            Self::set_meta(&conn, "embed_template", template.as_str())?;
+        }
         Self::set_meta(&conn, "fts_version", FTS_VERSION)?;
         Ok(())
     }
@@ -143,13 +212,25 @@ impl Store {
         let conn = Self::open_connection(path, mode)?;
         Self::apply_pragmas(&conn, mode)?;
 
+        let root = path.parent().unwrap_or(Path::new("."));
+        let root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
         let version = Self::schema_version(&conn)?;
-        if version != SCHEMA_VERSION {
+        if !(MIN_MIGRATABLE_VERSION..=SCHEMA_VERSION).contains(&version) {
             anyhow::bail!(
                 "store schema version {} unsupported; re-init + re-ingest required",
                 version
             );
         }
+        if version < SCHEMA_VERSION {
+            if matches!(mode, StoreMode::ReadOnly) {
+                anyhow::bail!(
+                    "store schema version {} requires migration to {}; run a write command (e.g. `recall doctor --fix`) first",
+                    version,
+                    SCHEMA_VERSION
+                );
+            }
+            Self::migrate(&conn, version, &root)?;
+        }
 
         let embedding = resolve_embedding(config)?;
         let embedding_dim = embedding.dim;
@@ -158,11 +239,16 @@ impl Store {
         }
         Self::validate_embedding(&conn, embedding)?;
         Self::validate_embedding_dim(&conn, embedding_dim)?;
+        Self::validate_tokenizer(&conn, resolve_tokenizer(config)?)?;
+        Self::validate_embed_template(&conn, EmbedTemplate::from_config(config)?.as_ref())?;
 
         Ok(Self {
             conn,
             path: path.to_path_buf(),
+            root,
             lock: Some(lock),
+            simhashes: RefCell::new(None),
+            languages: RefCell::new(None),
         })
     }
 
@@ -238,12 +324,73 @@ impl Store {
     fn create_schema(conn: &Connection, embedding_dim: usize) -> Result<()> {
         let dim = embedding_dim.max(1);
         let sql = format!(
-            "CREATE TABLE IF NOT EXISTS meta (\n  key TEXT PRIMARY KEY,\n  value TEXT\n);\n\nCREATE TABLE IF NOT EXISTS doc (\n  id TEXT PRIMARY KEY,\n  path TEXT,\n  mtime TEXT,\n  size INTEGER,\n  hash TEXT,\n  tag TEXT,\n  source TEXT,\n  meta TEXT,\n  deleted INTEGER DEFAULT 0\n);\n\nCREATE TABLE IF NOT EXISTS chunk (\n  rowid INTEGER PRIMARY KEY,\n  id TEXT UNIQUE,\n  doc_id TEXT,\n  offset INTEGER,\n  tokens INTEGER,\n  text TEXT,\n  embedding BLOB,\n  deleted INTEGER DEFAULT 0\n);\n\nCREATE INDEX IF NOT EXISTS idx_doc_path ON doc(path);\nCREATE INDEX IF NOT EXISTS idx_doc_tag ON doc(tag);\nCREATE INDEX IF NOT EXISTS idx_chunk_doc ON chunk(doc_id);\n\nCREATE VIRTUAL TABLE IF NOT EXISTS chunk_fts USING fts5(text, content='chunk', content_rowid='rowid');\n\nCREATE VIRTUAL TABLE IF NOT EXISTS chunk_vec USING vec0(\n  chunk_rowid INTEGER PRIMARY KEY,\n  embedding FLOAT[{dim}] distance_metric=cosine\n);\n\nCREATE TRIGGER IF NOT EXISTS chunk_ai AFTER INSERT ON chunk BEGIN\n  INSERT INTO chunk_fts(rowid, text) VALUES (new.rowid, new.text);\nEND;\n\nCREATE TRIGGER IF NOT EXISTS chunk_ad AFTER DELETE ON chunk BEGIN\n  INSERT INTO chunk_fts(chunk_fts, rowid, text) VALUES('delete', old.rowid, old.text);\nEND;\n\nCREATE TRIGGER IF NOT EXISTS chunk_au AFTER UPDATE ON chunk BEGIN\n  INSERT INTO chunk_fts(chunk_fts, rowid, text) VALUES('delete', old.rowid, old.text);\n  INSERT INTO chunk_fts(rowid, text) VALUES (new.rowid, new.text);\nEND;"
+            "CREATE TABLE IF NOT EXISTS meta (\n  key TEXT PRIMARY KEY,\n  value TEXT\n);\n\nCREATE TABLE IF NOT EXISTS doc (\n  id TEXT PRIMARY KEY,\n  path TEXT,\n  mtime TEXT,\n  size INTEGER,\n  hash TEXT,\n  tag TEXT,\n  source TEXT,\n  meta TEXT,\n  chunking TEXT,\n  origin TEXT,\n  simhash INTEGER,\n  lang TEXT,\n  title TEXT,\n  deleted INTEGER DEFAULT 0\n);\n\nCREATE TABLE IF NOT EXISTS chunk (\n  rowid INTEGER PRIMARY KEY,\n  id TEXT UNIQUE,\n  doc_id TEXT,\n  offset INTEGER,\n  tokens INTEGER,\n  text TEXT,\n  embedding BLOB,\n  meta TEXT,\n  parent_id TEXT,\n  simhash INTEGER,\n  lang TEXT,\n  terms TEXT,\n  section TEXT,\n  deleted INTEGER DEFAULT 0\n);\n\nCREATE TABLE IF NOT EXISTS chunk_parent (\n  id TEXT PRIMARY KEY,\n  doc_id TEXT,\n  offset INTEGER,\n  tokens INTEGER,\n  text TEXT\n);\n\nCREATE INDEX IF NOT EXISTS idx_doc_path ON doc(path);\nCREATE INDEX IF NOT EXISTS idx_doc_tag ON doc(tag);\nCREATE INDEX IF NOT EXISTS idx_chunk_doc ON chunk(doc_id);\nCREATE INDEX IF NOT EXISTS idx_chunk_lang ON chunk(lang);\nCREATE INDEX IF NOT EXISTS idx_chunk_parent_doc ON chunk_parent(doc_id);\n\nCREATE TABLE IF NOT EXISTS doc_tag (\n  doc_id TEXT NOT NULL,\n  tag TEXT NOT NULL,\n  PRIMARY KEY (doc_id, tag)\n);\nCREATE INDEX IF NOT EXISTS idx_doc_tag_tag ON doc_tag(tag);\n\nCREATE TABLE IF NOT EXISTS link (\n  source_id TEXT NOT NULL,\n  target TEXT NOT NULL,\n  anchor TEXT,\n  kind TEXT NOT NULL\n);\nCREATE INDEX IF NOT EXISTS idx_link_source ON link(source_id);\nCREATE INDEX IF NOT EXISTS idx_link_target ON link(target);\n\nCREATE TABLE IF NOT EXISTS symbol (\n  doc_id TEXT NOT NULL,\n  chunk_id TEXT,\n  name TEXT NOT NULL,\n  kind TEXT NOT NULL,\n  line INTEGER NOT NULL,\n  text TEXT\n);\nCREATE INDEX IF NOT EXISTS idx_symbol_name ON symbol(name);\nCREATE INDEX IF NOT EXISTS idx_symbol_doc ON symbol(doc_id);\nCREATE INDEX IF NOT EXISTS idx_symbol_chunk ON symbol(chunk_id);\n\nCREATE VIRTUAL TABLE IF NOT EXISTS chunk_fts USING fts5(text, terms, content='chunk', content_rowid='rowid');\n\nCREATE VIRTUAL TABLE IF NOT EXISTS chunk_vec USING vec0(\n  chunk_rowid INTEGER PRIMARY KEY,\n  embedding FLOAT[{dim}] distance_metric=cosine\n);\n\nCREATE TRIGGER IF NOT EXISTS chunk_ai AFTER INSERT ON chunk BEGIN\n  INSERT INTO chunk_fts(rowid, text, terms) VALUES (new.rowid, new.text, new.terms);\nEND;\n\nCREATE TRIGGER IF NOT EXISTS chunk_ad AFTER DELETE ON chunk BEGIN\n  INSERT INTO chunk_fts(chunk_fts, rowid, text, terms) VALUES('delete', old.rowid, old.text, old.terms);\nEND;\n\nCREATE TRIGGER IF NOT EXISTS chunk_au AFTER UPDATE ON chunk BEGIN\n  INSERT INTO chunk_fts(chunk_fts, rowid, text, terms) VALUES('delete', old.rowid, old.text, old.terms);\n  INSERT INTO chunk_fts(rowid, text, terms) VALUES (new.rowid, new.text, new.terms);\nEND;"
         );
         conn.execute_batch(&sql).context("create schema")?;
         Ok(())
     }
 
+    fn migrate(conn: &Connection, from: i64, root: &Path) -> Result<()> {
+        conn.execute_batch("BEGIN IMMEDIATE")?;
+        let res = (|| -> Result<()> {
+            for (version, sql) in MIGRATIONS.iter().filter(|(v, _)| *v > from) {
+                conn.execute_batch(sql)
+                    .with_context(|| format!("migrate schema to version {version}"))?;
+                if *version == 9 {
+                    Self::relativize_doc_paths(conn, root)
+                        .context("migrate schema to version 9")?;
+                }
+                Self::set_meta(conn, "schema_version", &version.to_string())?;
+            }
+            Ok(())
+        })();
+        if res.is_err() {
+            conn.execute_batch("ROLLBACK")?;
+            return res;
+        }
+        conn.execute_batch("COMMIT")?;
+        Ok(())
+    }
+
+    /// Rewrite doc paths from before version 9 relative to `root`. Absolute paths under the root
+    /// become relative; relative paths are kept when they exist under the root, else resolved
+    /// against the working directory. Paths that stay outside the root are kept and reported.
+    fn relativize_doc_paths(conn: &Connection, root: &Path) -> Result<()> {
+        let mut stmt = conn.prepare("SELECT DISTINCT path FROM doc WHERE path IS NOT NULL")?;
+        let paths = stmt
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        let mut outside = Vec::new();
+        for stored in paths {
+            // Record and archive entry paths live on disk as their containing file.
+            let on_disk = stored
+                .split('#')
+                .next()
+                .and_then(|file| file.split(crate::archive::ENTRY_SEPARATOR).next())
+                .unwrap_or(&stored);
+            if Path::new(on_disk).is_relative() && root.join(on_disk).exists() {
+                continue;
+            }
+            let normalized = crate::paths::normalize(root, Path::new(&stored));
+            if Path::new(&normalized.path).is_absolute() {
+                outside.push(stored);
+                continue;
+            }
+            conn.execute(
+                "UPDATE doc SET origin = COALESCE(origin, ?3), path = ?2 WHERE path = ?1",
+                params![stored, normalized.path, normalized.origin],
+            )?;
+        }
+        if let Some(first) = outside.first() {
+            eprintln!(
+                "warning: {} doc path(s) are not under the store root {} and were left as-is (e.g. {first}); re-add them from the store directory",
+                outside.len(),
+                root.display()
+            );
+        }
+        Ok(())
+    }
+
     fn set_meta(conn: &Connection, key: &str, value: &str) -> Result<()> {
         conn.execute(
             "INSERT OR REPLACE INTO meta (key, value) VALUES (?1, ?2)",
@@ -317,6 +464,49 @@ impl Store {
         Ok(())
     }
 
+    /// Stores created before tokenizers were configurable counted whitespace words.
+    fn validate_tokenizer(conn: &Connection, tokenizer: &str) -> Result<()> {
+        let stored: Option<String> = conn
+            .query_row("SELECT value FROM meta WHERE key='tokenizer'", [], |row| {
+                row.get(0)
+            })
+            .optional()
+            .context("read tokenizer")?;
+        let stored = stored.as_deref().unwrap_or(TOKENIZER_WHITESPACE);
+        if stored != tokenizer {
+            anyhow::bail!(
+                "config tokenizer {} does not match store tokenizer {}; re-init + re-ingest required",
+                tokenizer,
+                stored
+            );
+        }
+        Ok(())
+    }
+
+    /// Chunk vectors were embedded through the store's template, so the config must name the same
+    /// one; stores without the key embed bare chunk text.
+    fn validate_embed_template(conn: &Connection, template: Option<&EmbedTemplate>) -> Result<()> {
+        let stored: Option<String> = conn
+            .query_row(
+                "SELECT value FROM meta WHERE key='embed_template'",
+                [],
+                |row| row.get(0),
+            )
+            .optional()
+            .context("read embed_template")?;
+        let template = template.map(EmbedTemplate::as_str);
+        if stored.as_deref() != template {
+            let describe =
+                |raw: Option<&str>| raw.map_or("none".to_string(), |raw| format!("{raw:?}"));
+            anyhow::bail!(
+                "config embed_template {} does not match store embed_template {}; re-init + re-ingest required",
+                describe(template),
+                describe(stored.as_deref())
+            );
+        }
+        Ok(())
+    }
+
     fn validate_embedding_dim(conn: &Connection, embedding_dim: usize) -> Result<()> {
         let Some(stored) = Self::embedding_dim_meta(conn)? else {
             anyhow::bail!("store embedding_dim metadata missing; re-init + re-ingest required");
@@ -460,18 +650,37 @@ impl Store {
         Ok(inserted)
     }
 
-    pub fn compact(&self) -> Result<()> {
+    pub fn compact(&self, progress: &Progress) -> Result<()> {
+        progress.phase("delete_tombstones");
         self.conn.execute("DELETE FROM chunk WHERE deleted=1", [])?;
         self.conn.execute("DELETE FROM doc WHERE deleted=1", [])?;
+        self.conn.execute(
+            "DELETE FROM doc_tag WHERE doc_id NOT IN (SELECT id FROM doc)",
+            [],
+        )?;
+        self.conn.execute(
+            "DELETE FROM chunk_parent WHERE doc_id NOT IN (SELECT id FROM doc)",
+            [],
+        )?;
+        self.conn.execute(
+            "DELETE FROM link WHERE source_id NOT IN (SELECT id FROM doc)",
+            [],
+        )?;
+        self.conn.execute(
+            "DELETE FROM symbol WHERE doc_id NOT IN (SELECT id FROM doc)",
+            [],
+        )?;
         self.conn.execute(
             "DELETE FROM chunk_vec WHERE chunk_rowid NOT IN (SELECT rowid FROM chunk)",
             [],
         )?;
+        progress.phase("vacuum");
         self.conn.execute_batch("VACUUM;")?;
         Ok(())
     }
 
-    pub fn mark_doc_deleted(&self, path: &str) -> Result<Vec<String>> {
+    /// Tombstone the live docs at `path`. Returns how many docs and chunks were tombstoned.
+    pub fn mark_doc_deleted(&self, path: &str) -> Result<(usize, usize)> {
         let mut stmt = self
             .conn
             .prepare("SELECT id FROM doc WHERE path = ?1 AND deleted = 0")?;
@@ -480,22 +689,477 @@ impl Store {
         for id in ids_iter {
             ids.push(id?);
         }
+        let mut chunks = 0;
+        for id in &ids {
+            self.conn
+                .execute("UPDATE doc SET deleted=1 WHERE id = ?1", params![id])?;
+            chunks += self.conn.execute(
+                "UPDATE chunk SET deleted=1 WHERE doc_id = ?1 AND deleted = 0",
+                params![id],
+            )?;
+            self.conn.execute(
+                "DELETE FROM chunk_vec WHERE chunk_rowid IN (SELECT rowid FROM chunk WHERE doc_id = ?1)",
+                params![id],
+            )?;
+        }
+        if let Some(index) = self.simhashes.borrow_mut().as_mut() {
+            index.remove(path);
+        }
+        Ok((ids.len(), chunks))
+    }
+
+    /// Live doc with this id whose recorded chunking matches. Docs written before chunking was
+    /// recorded count as current.
+    pub fn live_doc_current(&self, id: &str, chunking: &str) -> Result<bool> {
+        let current = self.conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM doc WHERE id = ?1 AND deleted = 0 AND (chunking IS NULL OR chunking = ?2))",
+            params![id, chunking],
+            |row| row.get::<_, bool>(0),
+        )?;
+        Ok(current)
+    }
+
+    pub fn update_doc_meta(&self, id: &str, meta: Option<&str>) -> Result<()> {
+        self.conn.execute(
+            "UPDATE doc SET meta = ?2 WHERE id = ?1 AND deleted = 0 AND meta IS NOT ?2",
+            params![id, meta],
+        )?;
+        Ok(())
+    }
+
+    /// Drop tombstoned rows that share a doc id so identical content can be re-ingested.
+    pub fn purge_deleted_doc(&self, id: &str) -> Result<()> {
+        self.conn.execute(
+            "DELETE FROM chunk WHERE doc_id = ?1 AND deleted = 1",
+            params![id],
+        )?;
+        self.conn.execute(
+            "DELETE FROM doc_tag WHERE doc_id = ?1 AND doc_id IN (SELECT id FROM doc WHERE deleted = 1)",
+            params![id],
+        )?;
+        self.conn.execute(
+            "DELETE FROM chunk_parent WHERE doc_id = ?1 AND doc_id IN (SELECT id FROM doc WHERE deleted = 1)",
+            params![id],
+        )?;
+        self.conn.execute(
+            "DELETE FROM link WHERE source_id = ?1 AND source_id IN (SELECT id FROM doc WHERE deleted = 1)",
+            params![id],
+        )?;
+        self.conn.execute(
+            "DELETE FROM symbol WHERE doc_id = ?1 AND doc_id IN (SELECT id FROM doc WHERE deleted = 1)",
+            params![id],
+        )?;
+        self.conn
+            .execute("DELETE FROM doc WHERE id = ?1 AND deleted = 1", params![id])?;
+        Ok(())
+    }
+
+    pub fn doc_path(&self, path: &Path) -> DocPath {
+        crate::paths::normalize(&self.root, path)
+    }
+
+    /// Live docs named by id, by stored path, or by any spelling of a path that normalizes to
+    /// a stored one.
+    pub fn resolve_docs(&self, target: &str) -> Result<Vec<String>> {
+        let ids = self.live_doc_ids(target)?;
         if !ids.is_empty() {
-            for id in &ids {
-                self.conn
-                    .execute("UPDATE doc SET deleted=1 WHERE id = ?1", params![id])?;
-                self.conn
-                    .execute("UPDATE chunk SET deleted=1 WHERE doc_id = ?1", params![id])?;
+            return Ok(ids);
+        }
+        self.live_doc_ids(&self.doc_path(Path::new(target)).path)
+    }
+
+    pub fn live_doc_ids(&self, path_or_id: &str) -> Result<Vec<String>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id FROM doc WHERE deleted = 0 AND (id = ?1 OR path = ?1) ORDER BY id",
+        )?;
+        let rows = stmt.query_map(params![path_or_id], |row| row.get::<_, String>(0))?;
+        let mut ids = Vec::new();
+        for id in rows {
+            ids.push(id?);
+        }
+        Ok(ids)
+    }
+
+    /// Tags and primary `doc.tag` of the live doc(s) at `path`, carried over on re-ingest.
+    pub fn live_doc_tags(&self, path: &str) -> Result<(Option<String>, Vec<String>)> {
+        let primary = self
+            .conn
+            .query_row(
+                "SELECT tag FROM doc WHERE path = ?1 AND deleted = 0 AND tag IS NOT NULL ORDER BY rowid DESC LIMIT 1",
+                params![path],
+                |row| row.get::<_, String>(0),
+            )
+            .optional()?;
+        let mut stmt = self.conn.prepare(
+            "SELECT DISTINCT doc_tag.tag FROM doc_tag JOIN doc ON doc.id = doc_tag.doc_id WHERE doc.path = ?1 AND doc.deleted = 0 ORDER BY doc_tag.tag",
+        )?;
+        let rows = stmt.query_map(params![path], |row| row.get::<_, String>(0))?;
+        let mut tags = Vec::new();
+        for tag in rows {
+            tags.push(tag?);
+        }
+        Ok((primary, tags))
+    }
+
+    /// Attach `tag` to a doc. The first tag also becomes `doc.tag`. Returns false if already set.
+    pub fn add_doc_tag(&self, doc_id: &str, tag: &str) -> Result<bool> {
+        let inserted = self.conn.execute(
+            "INSERT OR IGNORE INTO doc_tag (doc_id, tag) VALUES (?1, ?2)",
+            params![doc_id, tag],
+        )?;
+        self.conn.execute(
+            "UPDATE doc SET tag = ?2 WHERE id = ?1 AND tag IS NULL",
+            params![doc_id, tag],
+        )?;
+        Ok(inserted > 0)
+    }
+
+    /// Detach `tag` from a doc; `doc.tag` falls back to the smallest remaining tag.
+    pub fn remove_doc_tag(&self, doc_id: &str, tag: &str) -> Result<bool> {
+        let removed = self.conn.execute(
+            "DELETE FROM doc_tag WHERE doc_id = ?1 AND tag = ?2",
+            params![doc_id, tag],
+        )?;
+        self.conn.execute(
+            "UPDATE doc SET tag = (SELECT min(tag) FROM doc_tag WHERE doc_id = ?1) WHERE id = ?1 AND tag = ?2",
+            params![doc_id, tag],
+        )?;
+        Ok(removed > 0)
+    }
+
+    pub fn live_doc_paths_with_prefix(&self, prefix: &str) -> Result<Vec<String>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT DISTINCT path FROM doc WHERE deleted = 0 AND substr(path, 1, length(?1)) = ?1 ORDER BY path",
+        )?;
+        let rows = stmt.query_map(params![prefix], |row| row.get::<_, String>(0))?;
+        let mut paths = Vec::new();
+        for path in rows {
+            paths.push(path?);
+        }
+        Ok(paths)
+    }
+
+    pub fn replace_links(&self, doc_id: &str, links: &[Link]) -> Result<()> {
+        self.conn
+            .execute("DELETE FROM link WHERE source_id = ?1", params![doc_id])?;
+        for link in links {
+            self.conn.execute(
+                "INSERT INTO link (source_id, target, anchor, kind) VALUES (?1, ?2, ?3, ?4)",
+                params![doc_id, link.target, link.anchor, link.kind],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Rows for each symbol: one per chunk containing its line, or one without a chunk.
+    pub fn replace_symbols(&self, doc_id: &str, symbols: &[Symbol]) -> Result<()> {
+        self.conn
+            .execute("DELETE FROM symbol WHERE doc_id = ?1", params![doc_id])?;
+        let mut stmt = self.conn.prepare(
+            "INSERT INTO symbol (doc_id, chunk_id, name, kind, line, text) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        )?;
+        for symbol in symbols {
+            let text: String = symbol.text.chars().take(SYMBOL_TEXT_CHARS).collect();
+            let chunk_ids: Vec<Option<&str>> = if symbol.chunk_ids.is_empty() {
+                vec![None]
+            } else {
+                symbol
+                    .chunk_ids
+                    .iter()
+                    .map(|id| Some(id.as_str()))
+                    .collect()
+            };
+            for chunk_id in chunk_ids {
+                stmt.execute(params![
+                    doc_id,
+                    chunk_id,
+                    symbol.name,
+                    symbol.kind,
+                    symbol.line as i64,
+                    text
+                ])?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn has_symbols(&self, doc_id: &str) -> Result<bool> {
+        let found = self
+            .conn
+            .query_row(
+                "SELECT 1 FROM symbol WHERE doc_id = ?1 LIMIT 1",
+                params![doc_id],
+                |_| Ok(()),
+            )
+            .optional()?;
+        Ok(found.is_some())
+    }
+
+    /// Live chunk ids and texts of a doc in offset order.
+    pub fn doc_chunk_texts(&self, doc_id: &str) -> Result<Vec<(String, String)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, text FROM chunk WHERE doc_id = ?1 AND deleted = 0 ORDER BY offset",
+        )?;
+        let rows = stmt.query_map(params![doc_id], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        let mut chunks = Vec::new();
+        for row in rows {
+            chunks.push(row?);
+        }
+        Ok(chunks)
+    }
+
+    /// Definitions, then imports, then references named `name` in live docs.
+    pub fn find_symbols(&self, name: &str, kind: Option<&str>) -> Result<Vec<SymbolHit>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT DISTINCT symbol.name, symbol.kind, doc.id, doc.path, symbol.line, symbol.text FROM symbol JOIN doc ON doc.id = symbol.doc_id WHERE doc.deleted = 0 AND symbol.name = ?1 AND (?2 IS NULL OR symbol.kind = ?2) ORDER BY CASE symbol.kind WHEN 'def' THEN 0 WHEN 'import' THEN 1 ELSE 2 END, doc.path, symbol.line",
+        )?;
+        let rows = stmt.query_map(params![name, kind], |row| {
+            Ok(SymbolHit {
+                name: row.get(0)?,
+                kind: row.get(1)?,
+                doc_id: row.get(2)?,
+                path: row.get(3)?,
+                line: row.get(4)?,
+                text: row.get(5)?,
+            })
+        })?;
+        let mut hits = Vec::new();
+        for row in rows {
+            hits.push(row?);
+        }
+        Ok(hits)
+    }
+
+    /// Fingerprints of live docs, by path. Docs ingested before fingerprinting have none until
+    /// they are re-added.
+    pub fn doc_fingerprints(&self) -> Result<Vec<DupeMember>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, path, simhash FROM doc WHERE deleted = 0 AND simhash IS NOT NULL ORDER BY path",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok(DupeMember {
+                doc_id: row.get(0)?,
+                path: row.get(1)?,
+                chunk_id: None,
+                offset: None,
+                text: None,
+                simhash: row.get::<_, i64>(2)? as u64,
+            })
+        })?;
+        let mut docs = Vec::new();
+        for row in rows {
+            docs.push(row?);
+        }
+        Ok(docs)
+    }
+
+    /// Fingerprints of live chunks, by path and offset; missing ones are computed from the text,
+    /// and chunks without words are left out.
+    pub fn chunk_fingerprints(&self) -> Result<Vec<DupeMember>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT chunk.id, chunk.doc_id, doc.path, chunk.offset, chunk.text, chunk.simhash FROM chunk JOIN doc ON doc.id = chunk.doc_id WHERE chunk.deleted = 0 AND doc.deleted = 0 ORDER BY doc.path, chunk.offset",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            let text: String = row.get(4)?;
+            let stored: Option<i64> = row.get(5)?;
+            let Some(hash) = stored.map(|hash| hash as u64).or_else(|| simhash(&text)) else {
+                return Ok(None);
+            };
+            Ok(Some(DupeMember {
+                chunk_id: Some(row.get(0)?),
+                doc_id: row.get(1)?,
+                path: row.get(2)?,
+                offset: Some(row.get(3)?),
+                simhash: hash,
+                text: Some(text),
+            }))
+        })?;
+        let mut chunks = Vec::new();
+        for row in rows {
+            chunks.extend(row?);
+        }
+        Ok(chunks)
+    }
+
+    /// Path of the nearest live doc (ties broken by path) within `distance` bits of `hash`,
+    /// ignoring `path` itself and docs already marked as duplicates.
+    pub fn near_duplicate_doc(
+        &self,
+        path: &str,
+        hash: u64,
+        distance: u32,
+    ) -> Result<Option<String>> {
+        let mut cached = self.simhashes.borrow_mut();
+        if cached
+            .as_ref()
+            .is_none_or(|index| index.distance() != distance)
+        {
+            let mut index = SimhashIndex::new(distance);
+            let mut stmt = self.conn.prepare(
+                "SELECT path, simhash FROM doc WHERE deleted = 0 AND simhash IS NOT NULL AND json_extract(meta, '$.duplicate_of') IS NULL",
+            )?;
+            let rows = stmt.query_map([], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)? as u64))
+            })?;
+            for row in rows {
+                let (other, other_hash) = row?;
+                index.insert(&other, other_hash);
+            }
+            *cached = Some(index);
+        }
+        Ok(cached.as_ref().and_then(|index| index.nearest(path, hash)))
+    }
+
+    /// Keep the near-duplicate index in step with a doc just written at `path`: `None` when the
+    /// doc is marked as a duplicate and so must not be matched against.
+    pub fn index_simhash(&self, path: &str, hash: Option<u64>) {
+        if let Some(index) = self.simhashes.borrow_mut().as_mut() {
+            match hash {
+                Some(hash) => index.insert(path, hash),
+                None => index.remove(path),
+            }
+        }
+    }
+
+    /// Drop the near-duplicate index, e.g. after rolling back writes it was updated for.
+    pub fn forget_simhashes(&self) {
+        self.simhashes.replace(None);
+    }
+
+    /// Fill in fingerprints for a doc stored before they were recorded. Text without words
+    /// keeps a NULL fingerprint.
+    pub fn backfill_simhash(&self, doc_id: &str, hash: Option<u64>) -> Result<()> {
+        if let Some(hash) = hash {
+            self.conn.execute(
+                "UPDATE doc SET simhash = ?2 WHERE id = ?1 AND simhash IS NULL",
+                params![doc_id, hash as i64],
+            )?;
+        }
+        let mut stmt = self
+            .conn
+            .prepare("SELECT id, text FROM chunk WHERE doc_id = ?1 AND simhash IS NULL")?;
+        let rows = stmt.query_map(params![doc_id], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?;
+        for row in rows {
+            let (id, text) = row?;
+            if let Some(hash) = simhash(&text) {
                 self.conn.execute(
-                    "DELETE FROM chunk_vec WHERE chunk_rowid IN (SELECT rowid FROM chunk WHERE doc_id = ?1)",
-                    params![id],
+                    "UPDATE chunk SET simhash = ?2 WHERE id = ?1",
+                    params![id, hash as i64],
                 )?;
             }
         }
+        Ok(())
+    }
+
+    /// Fill in the title of a doc stored before titles were extracted.
+    pub fn backfill_title(&self, doc_id: &str, title: Option<&str>) -> Result<()> {
+        self.conn.execute(
+            "UPDATE doc SET title = ?2 WHERE id = ?1 AND title IS NULL",
+            params![doc_id, title],
+        )?;
+        Ok(())
+    }
+
+    /// Fill in heading breadcrumbs, keyed by chunk id, of chunks stored before sections existed.
+    pub fn backfill_sections(&self, sections: &[(String, &str)]) -> Result<()> {
+        let mut stmt = self
+            .conn
+            .prepare("UPDATE chunk SET section = ?2 WHERE id = ?1 AND section IS NULL")?;
+        for (id, section) in sections {
+            stmt.execute(params![id, section])?;
+        }
+        Ok(())
+    }
+
+    /// Fill in languages and FTS terms for a doc stored before detection existed.
+    pub fn backfill_lang(&self, doc_id: &str, doc_lang: Option<&'static str>) -> Result<()> {
+        self.conn.execute(
+            "UPDATE doc SET lang = ?2 WHERE id = ?1 AND lang IS NULL",
+            params![doc_id, doc_lang],
+        )?;
+        let mut stmt = self
+            .conn
+            .prepare("SELECT id, text FROM chunk WHERE doc_id = ?1 AND terms IS NULL")?;
+        let rows = stmt.query_map(params![doc_id], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?;
+        for row in rows {
+            let (id, text) = row?;
+            let lang = lang::chunk_lang(&text, doc_lang);
+            self.conn.execute(
+                "UPDATE chunk SET lang = ?2, terms = ?3 WHERE id = ?1",
+                params![id, lang, lang::index_terms(&text, lang)],
+            )?;
+            self.note_chunk_language(lang);
+        }
+        Ok(())
+    }
+
+    /// Languages of live chunks, for expanding lexical queries. Scans the chunk table once per
+    /// handle.
+    pub fn chunk_languages(&self) -> Result<Vec<String>> {
+        let mut cached = self.languages.borrow_mut();
+        if cached.is_none() {
+            let mut stmt = self.conn.prepare(
+                "SELECT DISTINCT lang FROM chunk WHERE deleted = 0 AND lang IS NOT NULL",
+            )?;
+            let rows = stmt.query_map([], |row| row.get(0))?;
+            let mut langs = BTreeSet::new();
+            for row in rows {
+                langs.insert(row?);
+            }
+            *cached = Some(langs);
+        }
+        Ok(cached.iter().flatten().cloned().collect())
+    }
+
+    /// Keep the cached language set in step with a chunk just written.
+    pub fn note_chunk_language(&self, lang: Option<&str>) {
+        if let (Some(lang), Some(langs)) = (lang, self.languages.borrow_mut().as_mut()) {
+            langs.insert(lang.to_string());
+        }
+    }
+
+    /// Live docs that `doc_id` links to, in link order.
+    pub fn linked_doc_ids(&self, doc_id: &str) -> Result<Vec<String>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT target_doc.id FROM link JOIN doc AS target_doc ON {} WHERE link.source_id = ?1 AND target_doc.deleted = 0 AND target_doc.id != ?1 ORDER BY link.rowid, target_doc.path",
+            link_resolves_to("target_doc")
+        ))?;
+        let rows = stmt.query_map(params![doc_id], |row| row.get::<_, String>(0))?;
+        let mut ids: Vec<String> = Vec::new();
+        for id in rows {
+            let id = id?;
+            if !ids.contains(&id) {
+                ids.push(id);
+            }
+        }
         Ok(ids)
     }
 
+    pub fn chunk_parent(&self, chunk_id: &str) -> Result<Option<ParentRow>> {
+        let parent = self
+            .conn
+            .query_row(
+                "SELECT p.id, p.doc_id, p.offset, p.text FROM chunk c JOIN chunk_parent p ON p.id = c.parent_id WHERE c.id = ?1",
+                params![chunk_id],
+                |row| {
+                    Ok(ParentRow {
+                        id: row.get(0)?,
+                        doc_id: row.get(1)?,
+                        offset: row.get(2)?,
+                        text: row.get(3)?,
+                    })
+                },
+            )
+            .optional()?;
+        Ok(parent)
+    }
+
     pub fn mark_doc_deleted_by_id(&self, id: &str) -> Result<usize> {
+        self.forget_simhashes();
         let updated = self
             .conn
             .execute("UPDATE doc SET deleted=1 WHERE id = ?1", params![id])?;
@@ -547,6 +1211,188 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn chunk_languages_are_scanned_once_per_handle() -> Result<()> {
+        let dir = tempdir()?;
+        let db_path = dir.path().join("recall.db");
+        let config = Config::default();
+        Store::init(&db_path, &config)?;
+        let store = Store::open(&db_path, StoreMode::ReadWrite, &config)?;
+        let insert = |id: &str, lang: &str| {
+            store.conn.execute(
+                "INSERT INTO chunk (id, doc_id, offset, tokens, text, lang, deleted) VALUES (?1, 'd1', 0, 1, 'x', ?2, 0)",
+                params![id, lang],
+            )
+        };
+        insert("c1", "en")?;
+        assert_eq!(store.chunk_languages()?, ["en"]);
+        // Not re-read from the table...
+        insert("c2", "de")?;
+        assert_eq!(store.chunk_languages()?, ["en"]);
+        // ...but kept in step with chunks the handle notes.
+        store.note_chunk_language(Some("de"));
+        assert_eq!(store.chunk_languages()?, ["de", "en"]);
+        Ok(())
+    }
+
+    #[test]
+    fn migrates_older_store_on_write_open() -> Result<()> {
+        let dir = tempdir()?;
+        let db_path = dir.path().join("recall.db");
+        let config = Config::default();
+        Store::init(&db_path, &config)?;
+        let conn = SqlConnection::open(&db_path)?;
+        conn.execute_batch(
+            "ALTER TABLE chunk DROP COLUMN meta;\nALTER TABLE chunk DROP COLUMN parent_id;\nALTER TABLE doc DROP COLUMN chunking;\nALTER TABLE doc DROP COLUMN origin;\nALTER TABLE doc DROP COLUMN simhash;\nALTER TABLE chunk DROP COLUMN simhash;\nDROP TRIGGER chunk_ai;\nDROP TRIGGER chunk_ad;\nDROP TRIGGER chunk_au;\nDROP TABLE chunk_fts;\nCREATE VIRTUAL TABLE chunk_fts USING fts5(text, content='chunk', content_rowid='rowid');\nDROP INDEX idx_chunk_lang;\nALTER TABLE doc DROP COLUMN lang;\nALTER TABLE chunk DROP COLUMN lang;\nALTER TABLE chunk DROP COLUMN terms;\nALTER TABLE doc DROP COLUMN title;\nALTER TABLE chunk DROP COLUMN section;\nINSERT INTO doc (id, path, deleted) VALUES ('d1', './docs/a.md', 0);\nUPDATE meta SET value = '2' WHERE key = 'schema_version';",
+        )?;
+        drop(conn);
+
+        let err = match Store::open(&db_path, StoreMode::ReadOnly, &config) {
+            Ok(_) => anyhow::bail!("expected migration error"),
+            Err(err) => err,
+        };
+        assert!(err.to_string().contains("requires migration"), "{err}");
+
+        let store = Store::open(&db_path, StoreMode::ReadWrite, &config)?;
+        assert_eq!(Store::schema_version(&store.conn)?, SCHEMA_VERSION);
+        store
+            .conn
+            .execute("UPDATE chunk SET meta = NULL WHERE 0", [])?;
+        store
+            .conn
+            .execute("UPDATE doc SET chunking = NULL WHERE 0", [])?;
+        store
+            .conn
+            .execute("UPDATE chunk SET parent_id = NULL WHERE 0", [])?;
+        store
+            .conn
+            .execute("UPDATE doc SET simhash = NULL WHERE 0", [])?;
+        store
+            .conn
+            .execute("UPDATE chunk SET terms = NULL WHERE 0", [])?;
+        store
+            .conn
+            .execute("UPDATE chunk SET section = NULL WHERE 0", [])?;
+        store
+            .conn
+            .execute("UPDATE doc SET title = NULL WHERE 0", [])?;
+        store.conn.execute(
+            "INSERT INTO chunk (id, text, terms) VALUES ('c1', 'a', 'b')",
+            [],
+        )?;
+        let indexed: i64 = store.conn.query_row(
+            "SELECT COUNT(*) FROM chunk_fts WHERE terms MATCH 'b'",
+            [],
+            |row| row.get(0),
+        )?;
+        assert_eq!(indexed, 1);
+        assert_eq!(store.live_doc_ids("docs/a.md")?, vec!["d1".to_string()]);
+        Ok(())
+    }
+
+    #[test]
+    fn migration_makes_doc_paths_relative_to_the_root() -> Result<()> {
+        let dir = tempdir()?;
+        let root = dir.path().canonicalize()?;
+        let db_path = root.join("recall.db");
+        let config = Config::default();
+        Store::init(&db_path, &config)?;
+        std::fs::create_dir(root.join("docs"))?;
+        std::fs::write(root.join("docs/a.md"), "a")?;
+        std::fs::write(root.join("rows.jsonl"), "{}")?;
+        let store = Store::open(&db_path, StoreMode::ReadWrite, &config)?;
+        let absolute = root.join("docs/a.md").to_string_lossy().into_owned();
+        let record = format!("{}#1", root.join("rows.jsonl").display());
+        for (id, path) in [
+            ("d1", absolute.as_str()),
+            ("d2", record.as_str()),
+            ("d3", "docs/a.md"),
+            ("d4", "/elsewhere/b.md"),
+        ] {
+            store.conn.execute(
+                "INSERT INTO doc (id, path, deleted) VALUES (?1, ?2, 0)",
+                params![id, path],
+            )?;
+        }
+
+        Store::relativize_doc_paths(&store.conn, &root)?;
+        let path = |id: &str| -> Result<(String, Option<String>)> {
+            Ok(store.conn.query_row(
+                "SELECT path, origin FROM doc WHERE id = ?1",
+                params![id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )?)
+        };
+        assert_eq!(path("d1")?, ("docs/a.md".to_string(), Some(absolute)));
+        assert_eq!(path("d2")?.0, "rows.jsonl#1");
+        assert_eq!(path("d3")?, ("docs/a.md".to_string(), None));
+        assert_eq!(path("d4")?, ("/elsewhere/b.md".to_string(), None));
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_tokenizer_mismatch() -> Result<()> {
+        let dir = tempdir()?;
+        let db_path = dir.path().join("recall.db");
+        Store::init(&db_path, &Config::default())?;
+        let config = Config {
+            tokenizer: "model2vec".to_string(),
+            ..Config::default()
+        };
+        let err = match Store::open(&db_path, StoreMode::ReadOnly, &config) {
+            Ok(_) => anyhow::bail!("expected tokenizer mismatch"),
+            Err(err) => err,
+        };
+        assert!(err.to_string().contains("tokenizer"), "{err}");
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_embed_template_mismatch() -> Result<()> {
+        let dir = tempdir()?;
+        let db_path = dir.path().join("recall.db");
+        let config = Config {
+            embed_template: Some("{title}\n{text}".to_string()),
+            ..Config::default()
+        };
+        Store::init(&db_path, &config)?;
+        Store::open(&db_path, StoreMode::ReadOnly, &config)?;
+        let err = match Store::open(&db_path, StoreMode::ReadOnly, &Config::default()) {
+            Ok(_) => anyhow::bail!("expected embed_template mismatch"),
+            Err(err) => err,
+        };
+        assert!(err.to_string().contains("embed_template"), "{err}");
+        Ok(())
+    }
+
+    #[test]
+    fn doc_tags_track_primary_tag() -> Result<()> {
+        let dir = tempdir()?;
+        let db_path = dir.path().join("recall.db");
+        Store::init(&db_path, &Config::default())?;
+        let store = Store::open(&db_path, StoreMode::ReadWrite, &Config::default())?;
+        store.conn.execute(
+            "INSERT INTO doc (id, path, mtime, size, hash, deleted) VALUES ('d1', 'a.md', '', 0, 'h', 0)",
+            [],
+        )?;
+        assert!(store.add_doc_tag("d1", "kb")?);
+        assert!(store.add_doc_tag("d1", "api")?);
+        assert!(!store.add_doc_tag("d1", "api")?);
+        assert_eq!(
+            store.live_doc_tags("a.md")?,
+            (
+                Some("kb".to_string()),
+                vec!["api".to_string(), "kb".to_string()]
+            )
+        );
+        assert!(store.remove_doc_tag("d1", "kb")?);
+        assert_eq!(
+            store.live_doc_tags("a.md")?,
+            (Some("api".to_string()), vec!["api".to_string()])
+        );
+        Ok(())
+    }
+
     #[test]
     fn rejects_unversioned_store() -> Result<()> {
         let dir = tempdir()?;
@@ -0,0 +1,336 @@
+// Copyright 2026 Recall Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Line-based symbol extraction for code docs: definitions, imports, and call references.
+
+/// A definition (`fn`, `def`, `class`, `struct`, ...).
+pub const SYMBOL_DEF: &str = "def";
+/// A name brought in by `use`, `import`, `from ... import`, or `#include`.
+pub const SYMBOL_IMPORT: &str = "import";
+/// A call site: an identifier directly followed by `(`.
+pub const SYMBOL_REF: &str = "ref";
+
+const MODIFIERS: [&str; 16] = [
+    "pub(crate) ",
+    "pub(super) ",
+    "pub ",
+    "export default ",
+    "export ",
+    "async ",
+    "unsafe ",
+    "const ",
+    "static ",
+    "public ",
+    "private ",
+    "protected ",
+    "internal ",
+    "abstract ",
+    "final ",
+    "override ",
+];
+
+const DEF_KEYWORDS: [&str; 11] = [
+    "fn ",
+    "def ",
+    "class ",
+    "struct ",
+    "enum ",
+    "trait ",
+    "interface ",
+    "type ",
+    "func ",
+    "function ",
+    "union ",
+];
+
+/// Keywords and constructors that look like calls but are not references.
+const NOT_CALLS: [&str; 18] = [
+    "if", "for", "while", "match", "switch", "return", "catch", "fn", "def", "function", "func",
+    "sizeof", "typeof", "elif", "assert", "Some", "Ok", "Err",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Symbol {
+    pub name: String,
+    pub kind: &'static str,
+    /// 1-based line number in the doc.
+    pub line: usize,
+    /// The trimmed source line.
+    pub text: String,
+    /// Chunks whose text contains the line; filled by `locate`.
+    pub chunk_ids: Vec<String>,
+}
+
+/// Symbols in `text`, in line order.
+pub fn extract_symbols(text: &str) -> Vec<Symbol> {
+    let mut symbols = Vec::new();
+    let mut in_block_comment = false;
+    for (index, raw) in text.lines().enumerate() {
+        let line = raw.trim();
+        if in_block_comment {
+            in_block_comment = !line.contains("*/");
+            continue;
+        }
+        if line.starts_with("/*") {
+            in_block_comment = !line.contains("*/");
+            continue;
+        }
+        let is_comment = line.starts_with("//")
+            || line.starts_with('*')
+            || (line.starts_with('#') && !line.starts_with("#include"));
+        if line.is_empty() || is_comment {
+            continue;
+        }
+        let mut push = |name: &str, kind: &'static str| {
+            symbols.push(Symbol {
+                name: name.to_string(),
+                kind,
+                line: index + 1,
+                text: line.to_string(),
+                chunk_ids: Vec::new(),
+            });
+        };
+        if let Some(names) = import_names(line) {
+            for name in names {
+                push(&name, SYMBOL_IMPORT);
+            }
+            continue;
+        }
+        let defined = definition_name(line);
+        if let Some(name) = defined {
+            push(name, SYMBOL_DEF);
+        }
+        let mut seen: Vec<&str> = Vec::new();
+        for name in call_names(line) {
+            if Some(name) != defined && !NOT_CALLS.contains(&name) && !seen.contains(&name) {
+                seen.push(name);
+                push(name, SYMBOL_REF);
+            }
+        }
+    }
+    symbols
+}
+
+/// Record which chunks contain each symbol's line. Whitespace is compared loosely because the
+/// whitespace tokenizer rejoins chunk text with single spaces.
+pub fn locate(symbols: &mut [Symbol], chunks: &[(String, String)]) {
+    let chunks: Vec<(&str, String)> = chunks
+        .iter()
+        .map(|(id, text)| (id.as_str(), normalize(text)))
+        .collect();
+    // Symbols and chunks are both in document order, so the search resumes at the last hit.
+    let mut cursor = 0usize;
+    for symbol in symbols {
+        let line = normalize(&symbol.text);
+        let Some(first) = chunks[cursor..]
+            .iter()
+            .position(|(_, text)| text.contains(&line))
+        else {
+            continue;
+        };
+        cursor += first;
+        symbol.chunk_ids = chunks[cursor..]
+            .iter()
+            .take_while(|(_, text)| text.contains(&line))
+            .map(|(id, _)| id.to_string())
+            .collect();
+    }
+}
+
+fn normalize(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn definition_name(line: &str) -> Option<&str> {
+    let mut rest = line;
+    while let Some(stripped) = MODIFIERS.iter().find_map(|m| rest.strip_prefix(m)) {
+        rest = stripped;
+    }
+    let mut rest = DEF_KEYWORDS.iter().find_map(|k| rest.strip_prefix(k))?;
+    rest = rest.trim_start_matches('*').trim_start();
+    // Go methods: `func (r *Reader) Read(...)`.
+    if let Some(receiver) = rest.strip_prefix('(') {
+        rest = receiver.split_once(')')?.1.trim_start();
+    }
+    let name = leading_ident(rest);
+    (!name.is_empty()).then_some(name)
+}
+
+fn import_names(line: &str) -> Option<Vec<String>> {
+    let line = line.trim_end_matches(';');
+    if let Some(path) = line
+        .strip_prefix("use ")
+        .or_else(|| line.strip_prefix("pub use "))
+    {
+        return Some(rust_use_names(path));
+    }
+    if let Some(header) = line.strip_prefix("#include") {
+        let header = header.trim().trim_matches(['<', '>', '"']);
+        return Some(vec![header.to_string()]);
+    }
+    if let Some(rest) = line.strip_prefix("from ") {
+        let (_, names) = rest.split_once(" import ")?;
+        return Some(name_list(names.trim_matches(['(', ')', ' '])));
+    }
+    let rest = line.strip_prefix("import ")?;
+    if let Some((names, _)) = rest.split_once(" from ") {
+        // JavaScript: `import Default, { a, b as c } from 'mod'`.
+        let names = names.replace(['{', '}'], ",");
+        return Some(name_list(&names));
+    }
+    let unquoted = rest.trim_matches(['"', '\'', '`']);
+    if unquoted.len() != rest.len() {
+        return Some(vec![unquoted.to_string()]);
+    }
+    Some(name_list(rest))
+}
+
+/// `a::b::{c, d as e}` imports `c` and `d`; `a::b` imports `b`.
+fn rust_use_names(path: &str) -> Vec<String> {
+    match path.split_once('{') {
+        Some((_, group)) => name_list(&group.replace(['{', '}'], ","))
+            .into_iter()
+            .map(|name| last_segment(&name).to_string())
+            .filter(|name| name != "self")
+            .collect(),
+        None => name_list(path)
+            .iter()
+            .map(|name| last_segment(name).to_string())
+            .collect(),
+    }
+}
+
+fn last_segment(path: &str) -> &str {
+    path.rsplit("::").next().unwrap_or(path)
+}
+
+/// Comma-separated names with `as` aliases and globs dropped.
+fn name_list(raw: &str) -> Vec<String> {
+    raw.split(',')
+        .filter_map(|item| {
+            let item = item.trim();
+            let item = match item.strip_prefix("* as ") {
+                Some(alias) => alias,
+                None => item.split(" as ").next().unwrap_or(""),
+            };
+            let item = item.trim().trim_start_matches("type ");
+            (!item.is_empty() && !item.ends_with('*')).then(|| item.to_string())
+        })
+        .collect()
+}
+
+/// Identifiers directly followed by `(`, outside string literals and trailing comments.
+fn call_names(line: &str) -> Vec<&str> {
+    let mut names = Vec::new();
+    let bytes = line.as_bytes();
+    let mut index = 0;
+    let mut quote: Option<u8> = None;
+    while index < bytes.len() {
+        let byte = bytes[index];
+        if let Some(q) = quote {
+            if byte == b'\\' {
+                index += 1;
+            } else if byte == q {
+                quote = None;
+            }
+            index += 1;
+            continue;
+        }
+        if byte == b'"' || byte == b'`' {
+            quote = Some(byte);
+            index += 1;
+            continue;
+        }
+        if bytes[index..].starts_with(b"//") || bytes[index..].starts_with(b" #") {
+            break;
+        }
+        if byte.is_ascii_alphabetic() || byte == b'_' {
+            let name = leading_ident(&line[index..]);
+            index += name.len();
+            if bytes.get(index) == Some(&b'(') {
+                names.push(name);
+            }
+            continue;
+        }
+        index += 1;
+    }
+    names
+}
+
+fn leading_ident(text: &str) -> &str {
+    let end = text
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '$'))
+        .unwrap_or(text.len());
+    &text[..end]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(symbols: &[Symbol], kind: &str) -> Vec<String> {
+        symbols
+            .iter()
+            .filter(|symbol| symbol.kind == kind)
+            .map(|symbol| symbol.name.clone())
+            .collect()
+    }
+
+    #[test]
+    fn extracts_definitions_imports_and_calls() {
+        let text = "use crate::query::{self, search_chunks as search};\nuse std::fs;\n\n/// Runs a search.\npub fn run(store: &Store) -> Result<()> {\n    let hits = search_chunks(store, \"f(x)\")?; // helper()\n    if (hits.is_empty()) { return Ok(()) }\n}\n\nfrom os.path import join, exists\nimport numpy as np\nimport { a, b as c } from './mod'\n#include <stdio.h>\nfunc (r *Reader) Read(p []byte) {}\n";
+        let symbols = extract_symbols(text);
+        assert_eq!(names(&symbols, SYMBOL_DEF), vec!["run", "Read"]);
+        assert_eq!(
+            names(&symbols, SYMBOL_IMPORT),
+            vec![
+                "search_chunks",
+                "fs",
+                "join",
+                "exists",
+                "numpy",
+                "a",
+                "b",
+                "stdio.h"
+            ]
+        );
+        assert_eq!(
+            names(&symbols, SYMBOL_REF),
+            vec!["search_chunks", "is_empty"]
+        );
+        assert_eq!(symbols[3].line, 6);
+    }
+
+    #[test]
+    fn locate_matches_lines_across_whitespace() {
+        let mut symbols = extract_symbols("fn a() {}\n\nfn  b() {\n    a()\n}\n");
+        let chunks = vec![
+            ("c1".to_string(), "fn a() {}".to_string()),
+            ("c2".to_string(), "fn b() { a() }".to_string()),
+        ];
+        locate(&mut symbols, &chunks);
+        let located: Vec<(&str, &str, Vec<String>)> = symbols
+            .iter()
+            .map(|s| (s.kind, s.name.as_str(), s.chunk_ids.clone()))
+            .collect();
+        assert_eq!(
+            located,
+            vec![
+                (SYMBOL_DEF, "a", vec!["c1".to_string()]),
+                (SYMBOL_DEF, "b", vec!["c2".to_string()]),
+                (SYMBOL_REF, "a", vec!["c2".to_string()]),
+            ]
+        );
+    }
+}
@@ -0,0 +1,148 @@
+// Copyright 2026 Recall Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Token counting for chunk sizes, `chunk.tokens`, and context budgets.
+
+use std::ops::Range;
+use std::sync::OnceLock;
+
+use anyhow::Result;
+
+use crate::config::Config;
+use crate::embed::MODEL2VEC_TOKENIZER;
+
+pub const TOKENIZER_WHITESPACE: &str = "whitespace";
+pub const TOKENIZER_MODEL2VEC: &str = "model2vec";
+
+pub trait Tokenizer {
+    /// Byte ranges of each token in `text`.
+    fn spans(&self, text: &str) -> Vec<Range<usize>>;
+
+    /// Text covering a contiguous run of token spans.
+    fn join(&self, text: &str, spans: &[Range<usize>]) -> String {
+        match (spans.first(), spans.last()) {
+            (Some(first), Some(last)) => text[first.start..last.end].to_string(),
+            _ => String::new(),
+        }
+    }
+}
+
+pub fn resolve_tokenizer(config: &Config) -> Result<&'static str> {
+    match config.tokenizer.trim().to_lowercase().as_str() {
+        TOKENIZER_WHITESPACE => Ok(TOKENIZER_WHITESPACE),
+        TOKENIZER_MODEL2VEC => Ok(TOKENIZER_MODEL2VEC),
+        _ => anyhow::bail!(
+            "unsupported tokenizer '{}'; supported: {} or {}",
+            config.tokenizer,
+            TOKENIZER_WHITESPACE,
+            TOKENIZER_MODEL2VEC
+        ),
+    }
+}
+
+pub fn build_tokenizer(config: &Config) -> Result<Box<dyn Tokenizer>> {
+    match resolve_tokenizer(config)? {
+        TOKENIZER_MODEL2VEC => Ok(Box::new(Model2VecTokenizer::new()?)),
+        _ => Ok(Box::new(WhitespaceTokenizer)),
+    }
+}
+
+/// Whitespace-separated words; chunks are re-joined with single spaces.
+pub struct WhitespaceTokenizer;
+
+impl Tokenizer for WhitespaceTokenizer {
+    fn spans(&self, text: &str) -> Vec<Range<usize>> {
+        let mut spans = Vec::new();
+        let mut start = None;
+        for (idx, ch) in text.char_indices() {
+            match (ch.is_whitespace(), start) {
+                (true, Some(s)) => {
+                    spans.push(s..idx);
+                    start = None;
+                }
+                (false, None) => start = Some(idx),
+                _ => {}
+            }
+        }
+        if let Some(s) = start {
+            spans.push(s..text.len());
+        }
+        spans
+    }
+
+    fn join(&self, text: &str, spans: &[Range<usize>]) -> String {
+        spans
+            .iter()
+            .map(|span| &text[span.clone()])
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Subword tokens from the embedded model2vec `tokenizer.json`.
+#[derive(Clone, Copy)]
+pub struct Model2VecTokenizer {
+    inner: &'static tokenizers::Tokenizer,
+}
+
+impl Model2VecTokenizer {
+    pub fn new() -> Result<Self> {
+        static TOKENIZER: OnceLock<tokenizers::Tokenizer> = OnceLock::new();
+        if let Some(inner) = TOKENIZER.get() {
+            return Ok(Self { inner });
+        }
+        let tokenizer = tokenizers::Tokenizer::from_bytes(MODEL2VEC_TOKENIZER)
+            .map_err(|err| anyhow::anyhow!("load model2vec tokenizer: {err}"))?;
+        Ok(Self {
+            inner: TOKENIZER.get_or_init(|| tokenizer),
+        })
+    }
+}
+
+impl Tokenizer for Model2VecTokenizer {
+    fn spans(&self, text: &str) -> Vec<Range<usize>> {
+        let Ok(encoding) = self.inner.encode(text, false) else {
+            return WhitespaceTokenizer.spans(text);
+        };
+        encoding
+            .get_offsets()
+            .iter()
+            .filter(|(start, end)| end > start)
+            .map(|(start, end)| *start..*end)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn whitespace_spans_match_split_whitespace() {
+        let text = "  alpha\tbeta\n\ngamma ";
+        let spans = WhitespaceTokenizer.spans(text);
+        assert_eq!(spans.len(), 3);
+        assert_eq!(WhitespaceTokenizer.join(text, &spans[1..]), "beta gamma");
+    }
+
+    #[test]
+    fn model2vec_counts_subwords() -> Result<()> {
+        let tokenizer = Model2VecTokenizer::new()?;
+        let text = "tokenization of 東京タワー";
+        let spans = tokenizer.spans(text);
+        assert!(spans.len() > text.split_whitespace().count());
+        assert_eq!(tokenizer.join(text, &spans), text);
+        Ok(())
+    }
+}
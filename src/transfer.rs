@@ -23,6 +23,9 @@ use base64::engine::general_purpose::STANDARD as BASE64;
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::dedup;
+use crate::progress::Progress;
+use crate::sql::DOC_TAGS_SQL;
 use crate::store::Store;
 
 #[derive(Debug, Serialize)]
@@ -36,6 +39,16 @@ struct ExportDoc {
     tag: Option<String>,
     source: Option<String>,
     meta: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    origin: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    simhash: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    lang: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    title: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -47,6 +60,45 @@ struct ExportChunk {
     tokens: i64,
     text: String,
     embedding: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    meta: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parent_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    lang: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    section: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct ExportParent {
+    r#type: String,
+    id: String,
+    doc_id: String,
+    offset: i64,
+    tokens: i64,
+    text: String,
+}
+
+#[derive(Debug, Serialize)]
+struct ExportLink {
+    r#type: String,
+    source_id: String,
+    target: String,
+    anchor: Option<String>,
+    kind: String,
+}
+
+#[derive(Debug, Serialize)]
+struct ExportSymbol {
+    r#type: String,
+    doc_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    chunk_id: Option<String>,
+    name: String,
+    kind: String,
+    line: i64,
+    text: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -62,6 +114,16 @@ enum ImportLine {
         tag: Option<String>,
         source: Option<String>,
         meta: Option<String>,
+        #[serde(default)]
+        tags: Vec<String>,
+        #[serde(default)]
+        origin: Option<String>,
+        #[serde(default)]
+        simhash: Option<i64>,
+        #[serde(default)]
+        lang: Option<String>,
+        #[serde(default)]
+        title: Option<String>,
     },
     #[serde(rename = "chunk")]
     Chunk {
@@ -71,6 +133,41 @@ enum ImportLine {
         tokens: i64,
         text: String,
         embedding: String,
+        #[serde(default)]
+        meta: Option<String>,
+        #[serde(default)]
+        parent_id: Option<String>,
+        #[serde(default)]
+        lang: Option<String>,
+        #[serde(default)]
+        section: Option<String>,
+    },
+    #[serde(rename = "parent")]
+    Parent {
+        id: String,
+        doc_id: String,
+        offset: i64,
+        tokens: i64,
+        text: String,
+    },
+    #[serde(rename = "link")]
+    Link {
+        source_id: String,
+        target: String,
+        #[serde(default)]
+        anchor: Option<String>,
+        kind: String,
+    },
+    #[serde(rename = "symbol")]
+    Symbol {
+        doc_id: String,
+        #[serde(default)]
+        chunk_id: Option<String>,
+        name: String,
+        kind: String,
+        line: i64,
+        #[serde(default)]
+        text: Option<String>,
     },
 }
 
@@ -84,9 +181,9 @@ pub fn export_store(store: &Store, mut writer: impl Write) -> Result<TransferSta
     let mut docs = 0usize;
     let mut chunks = 0usize;
 
-    let mut stmt = store.conn.prepare(
-        "SELECT id, path, mtime, size, hash, tag, source, meta FROM doc WHERE deleted=0",
-    )?;
+    let mut stmt = store.conn.prepare(&format!(
+        "SELECT id, path, mtime, size, hash, tag, source, meta, {DOC_TAGS_SQL}, origin, simhash, lang, title FROM doc WHERE deleted=0"
+    ))?;
     let rows = stmt.query_map([], |row| {
         Ok(ExportDoc {
             r#type: "doc".to_string(),
@@ -98,6 +195,11 @@ pub fn export_store(store: &Store, mut writer: impl Write) -> Result<TransferSta
             tag: row.get(5)?,
             source: row.get(6)?,
             meta: row.get(7)?,
+            tags: serde_json::from_str(&row.get::<_, String>(8)?).unwrap_or_default(),
+            origin: row.get(9)?,
+            simhash: row.get(10)?,
+            lang: row.get(11)?,
+            title: row.get(12)?,
         })
     })?;
     for row in rows {
@@ -107,9 +209,66 @@ pub fn export_store(store: &Store, mut writer: impl Write) -> Result<TransferSta
         docs += 1;
     }
 
-    let mut stmt = store
-        .conn
-        .prepare("SELECT id, doc_id, offset, tokens, text, embedding FROM chunk WHERE deleted=0")?;
+    let mut stmt = store.conn.prepare(
+        "SELECT p.id, p.doc_id, p.offset, p.tokens, p.text FROM chunk_parent p JOIN doc d ON d.id = p.doc_id WHERE d.deleted=0",
+    )?;
+    let rows = stmt.query_map([], |row| {
+        Ok(ExportParent {
+            r#type: "parent".to_string(),
+            id: row.get(0)?,
+            doc_id: row.get(1)?,
+            offset: row.get(2)?,
+            tokens: row.get(3)?,
+            text: row.get(4)?,
+        })
+    })?;
+    for row in rows {
+        let parent = row?;
+        let line = serde_json::to_string(&parent)?;
+        writeln!(writer, "{}", line)?;
+    }
+
+    let mut stmt = store.conn.prepare(
+        "SELECT l.source_id, l.target, l.anchor, l.kind FROM link l JOIN doc d ON d.id = l.source_id WHERE d.deleted=0 ORDER BY l.rowid",
+    )?;
+    let rows = stmt.query_map([], |row| {
+        Ok(ExportLink {
+            r#type: "link".to_string(),
+            source_id: row.get(0)?,
+            target: row.get(1)?,
+            anchor: row.get(2)?,
+            kind: row.get(3)?,
+        })
+    })?;
+    for row in rows {
+        let link = row?;
+        let line = serde_json::to_string(&link)?;
+        writeln!(writer, "{}", line)?;
+    }
+
+    let mut stmt = store.conn.prepare(
+        "SELECT s.doc_id, s.chunk_id, s.name, s.kind, s.line, s.text FROM symbol s JOIN doc d ON d.id = s.doc_id WHERE d.deleted=0 ORDER BY s.rowid",
+    )?;
+    let rows = stmt.query_map([], |row| {
+        Ok(ExportSymbol {
+            r#type: "symbol".to_string(),
+            doc_id: row.get(0)?,
+            chunk_id: row.get(1)?,
+            name: row.get(2)?,
+            kind: row.get(3)?,
+            line: row.get(4)?,
+            text: row.get(5)?,
+        })
+    })?;
+    for row in rows {
+        let symbol = row?;
+        let line = serde_json::to_string(&symbol)?;
+        writeln!(writer, "{}", line)?;
+    }
+
+    let mut stmt = store.conn.prepare(
+        "SELECT id, doc_id, offset, tokens, text, embedding, meta, parent_id, lang, section FROM chunk WHERE deleted=0",
+    )?;
     let rows = stmt.query_map([], |row| {
         let embedding: Vec<u8> = row.get(5)?;
         Ok(ExportChunk {
@@ -120,6 +279,10 @@ pub fn export_store(store: &Store, mut writer: impl Write) -> Result<TransferSta
             tokens: row.get(3)?,
             text: row.get(4)?,
             embedding: BASE64.encode(embedding),
+            meta: row.get(6)?,
+            parent_id: row.get(7)?,
+            lang: row.get(8)?,
+            section: row.get(9)?,
         })
     })?;
     for row in rows {
@@ -132,11 +295,16 @@ pub fn export_store(store: &Store, mut writer: impl Write) -> Result<TransferSta
     Ok(TransferStats { docs, chunks })
 }
 
-pub fn import_store(store: &Store, reader: impl std::io::Read) -> Result<TransferStats> {
+pub fn import_store(
+    store: &Store,
+    reader: impl std::io::Read,
+    progress: &Progress,
+) -> Result<TransferStats> {
     let mut docs = 0usize;
     let mut chunks = 0usize;
     let mut buf = BufReader::new(reader);
 
+    progress.phase("import");
     store.conn.execute_batch("BEGIN IMMEDIATE")?;
     let res = (|| -> Result<()> {
         let mut line = String::new();
@@ -146,6 +314,7 @@ pub fn import_store(store: &Store, reader: impl std::io::Read) -> Result<Transfe
             if bytes == 0 {
                 break;
             }
+            progress.import_line(bytes as u64, docs, chunks);
             let trimmed = line.trim();
             if trimmed.is_empty() {
                 continue;
@@ -162,11 +331,23 @@ pub fn import_store(store: &Store, reader: impl std::io::Read) -> Result<Transfe
                     tag,
                     source,
                     meta,
+                    tags,
+                    origin,
+                    simhash,
+                    lang,
+                    title,
                 } => {
                     store.conn.execute(
-                        "INSERT OR REPLACE INTO doc (id, path, mtime, size, hash, tag, source, meta, deleted) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, 0)",
-                        rusqlite::params![id, path, mtime, size, hash, tag, source, meta],
+                        "INSERT OR REPLACE INTO doc (id, path, mtime, size, hash, tag, source, meta, origin, simhash, lang, title, deleted) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, 0)",
+                        rusqlite::params![id, path, mtime, size, hash, tag, source, meta, origin, simhash, lang, title],
                     )?;
+                    // Link and symbol lines follow their doc; drop those of an earlier copy.
+                    store.replace_links(&id, &[])?;
+                    store.replace_symbols(&id, &[])?;
+                    // Exports that predate the tag table only carry `tag`.
+                    for tag in tags.iter().chain(tag.iter()) {
+                        store.add_doc_tag(&id, tag)?;
+                    }
                     docs += 1;
                 }
                 ImportLine::Chunk {
@@ -176,14 +357,61 @@ pub fn import_store(store: &Store, reader: impl std::io::Read) -> Result<Transfe
                     tokens,
                     text,
                     embedding,
+                    meta,
+                    parent_id,
+                    lang,
+                    section,
                 } => {
                     let bytes = BASE64.decode(embedding.as_bytes())?;
+                    // Chunk fingerprints and FTS terms are cheap to recompute, so exports leave
+                    // them out. Chunks without a language get terms on their next `recall add`.
+                    let fingerprint = dedup::simhash(&text).map(|hash| hash as i64);
+                    let terms = lang
+                        .as_ref()
+                        .map(|lang| crate::lang::index_terms(&text, Some(lang)));
                     store.conn.execute(
-                        "INSERT OR REPLACE INTO chunk (id, doc_id, offset, tokens, text, embedding, deleted) VALUES (?1, ?2, ?3, ?4, ?5, ?6, 0)",
-                        rusqlite::params![id, doc_id, offset, tokens, text, bytes],
+                        "INSERT OR REPLACE INTO chunk (id, doc_id, offset, tokens, text, embedding, meta, parent_id, simhash, lang, terms, section, deleted) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, 0)",
+                        rusqlite::params![id, doc_id, offset, tokens, text, bytes, meta, parent_id, fingerprint, lang, terms, section],
                     )?;
+                    store.note_chunk_language(lang.as_deref());
                     chunks += 1;
                 }
+                ImportLine::Parent {
+                    id,
+                    doc_id,
+                    offset,
+                    tokens,
+                    text,
+                } => {
+                    store.conn.execute(
+                        "INSERT OR REPLACE INTO chunk_parent (id, doc_id, offset, tokens, text) VALUES (?1, ?2, ?3, ?4, ?5)",
+                        rusqlite::params![id, doc_id, offset, tokens, text],
+                    )?;
+                }
+                ImportLine::Link {
+                    source_id,
+                    target,
+                    anchor,
+                    kind,
+                } => {
+                    store.conn.execute(
+                        "INSERT INTO link (source_id, target, anchor, kind) VALUES (?1, ?2, ?3, ?4)",
+                        rusqlite::params![source_id, target, anchor, kind],
+                    )?;
+                }
+                ImportLine::Symbol {
+                    doc_id,
+                    chunk_id,
+                    name,
+                    kind,
+                    line,
+                    text,
+                } => {
+                    store.conn.execute(
+                        "INSERT INTO symbol (doc_id, chunk_id, name, kind, line, text) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                        rusqlite::params![doc_id, chunk_id, name, kind, line, text],
+                    )?;
+                }
             }
         }
         Ok(())
@@ -195,6 +423,8 @@ pub fn import_store(store: &Store, reader: impl std::io::Read) -> Result<Transfe
     }
 
     store.conn.execute_batch("COMMIT")?;
+    progress.import_finished(docs, chunks);
+    progress.phase("rebuild_vec");
     store.rebuild_vec()?;
 
     Ok(TransferStats { docs, chunks })
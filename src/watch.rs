@@ -0,0 +1,289 @@
+// Copyright 2026 Recall Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! `recall watch`: re-ingest or tombstone files as filesystem notifications arrive.
+//!
+//! Events are debounced into batches. The store is opened for writing only while a batch is
+//! applied, so readers are not locked out between batches.
+
+use std::collections::BTreeSet;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::sync::mpsc::RecvTimeoutError;
+use std::time::Duration;
+
+use anyhow::Context;
+use anyhow::Result;
+use notify::RecursiveMode;
+use notify::Watcher;
+use serde_json::Value;
+use serde_json::json;
+use time::OffsetDateTime;
+use time::format_description::well_known::Rfc3339;
+use walkdir::WalkDir;
+
+use crate::config::Config;
+use crate::git::GitFiles;
+use crate::ingest::IngestOptions;
+use crate::ingest::IngestReport;
+use crate::ingest::Models;
+use crate::ingest::PathChange;
+use crate::ingest::build_models;
+use crate::ingest::sync_path;
+use crate::store::Store;
+use crate::store::StoreMode;
+
+struct WatchRoot {
+    /// Path as given on the command line; doc paths are built from it so they match `recall add`.
+    display: PathBuf,
+    canonical: PathBuf,
+}
+
+pub fn watch(
+    store_path: &Path,
+    config: &Config,
+    paths: Vec<PathBuf>,
+    opts: IngestOptions,
+    debounce: Duration,
+) -> Result<()> {
+    let models = build_models(config)?;
+    let store_canonical = std::fs::canonicalize(store_path)
+        .with_context(|| format!("resolve store {}", store_path.display()))?;
+
+    let mut roots = Vec::new();
+    for path in if paths.is_empty() {
+        vec![PathBuf::from(".")]
+    } else {
+        paths
+    } {
+        let canonical = std::fs::canonicalize(&path)
+            .with_context(|| format!("resolve watch path {}", path.display()))?;
+        roots.push(WatchRoot {
+            display: path,
+            canonical,
+        });
+    }
+
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx).context("start file watcher")?;
+    for root in &roots {
+        watcher
+            .watch(&root.canonical, RecursiveMode::Recursive)
+            .with_context(|| format!("watch {}", root.display.display()))?;
+    }
+    emit(json!({
+        "event": "watching",
+        "paths": roots.iter().map(|root| root.display.to_string_lossy()).collect::<Vec<_>>(),
+    }))?;
+
+    let mut pending: BTreeSet<PathBuf> = BTreeSet::new();
+    loop {
+        if pending.is_empty() {
+            match rx.recv() {
+                Ok(event) => collect(event, &store_canonical, &mut pending)?,
+                Err(_) => return Ok(()),
+            }
+        }
+        loop {
+            match rx.recv_timeout(debounce) {
+                Ok(event) => collect(event, &store_canonical, &mut pending)?,
+                Err(RecvTimeoutError::Timeout) => break,
+                Err(RecvTimeoutError::Disconnected) => return Ok(()),
+            }
+        }
+        if pending.is_empty() {
+            continue;
+        }
+        match apply_batch(
+            store_path,
+            &store_canonical,
+            config,
+            &models,
+            &roots,
+            &pending,
+            &opts,
+        ) {
+            Ok(()) => pending.clear(),
+            // Keep the batch and retry after the next quiet period (e.g. the store was locked).
+            Err(err) => emit(json!({ "event": "error", "message": err.to_string() }))?,
+        }
+    }
+}
+
+fn collect(
+    event: notify::Result<notify::Event>,
+    store_canonical: &Path,
+    pending: &mut BTreeSet<PathBuf>,
+) -> Result<()> {
+    let event = match event {
+        Ok(event) => event,
+        Err(err) => return emit(json!({ "event": "error", "message": err.to_string() })),
+    };
+    if event.kind.is_access() {
+        return Ok(());
+    }
+    for path in event.paths {
+        // The store and its WAL/journal sidecars change on every batch; never feed them back.
+        if is_store_file(&path, store_canonical) {
+            continue;
+        }
+        pending.insert(path);
+    }
+    Ok(())
+}
+
+fn apply_batch(
+    store_path: &Path,
+    store_canonical: &Path,
+    config: &Config,
+    models: &Models,
+    roots: &[WatchRoot],
+    pending: &BTreeSet<PathBuf>,
+    opts: &IngestOptions,
+) -> Result<()> {
+    let store = Store::open(store_path, StoreMode::ReadWrite, config)?;
+    let tx = store.conn.unchecked_transaction()?;
+    let mut events = Vec::new();
+    let mut report = IngestReport::default();
+
+    for changed in pending {
+        let Some(path) = doc_path(roots, changed) else {
+            continue;
+        };
+        let files = if path.is_dir() {
+            WalkDir::new(&path)
+                .into_iter()
+                .filter_map(|e| e.ok())
+                .filter(|e| e.file_type().is_file())
+                .map(|e| e.into_path())
+                .filter(|p| {
+                    std::fs::canonicalize(p).is_ok_and(|p| !is_store_file(&p, store_canonical))
+                })
+                .collect()
+        } else {
+            vec![path]
+        };
+        for file in files {
+            // A sidecar change re-syncs the file it describes.
+            let file = crate::meta::sidecar_owner(&file).unwrap_or(file);
+            let chunks_before = report.chunks_added;
+            // Loaded per file so commits made while watching show up in the metadata.
+            let git = if opts.git && file.is_file() {
+                GitFiles::load(&file)
+                    .map_err(|err| {
+                        report.warnings.push(format!(
+                            "git metadata unavailable: {} ({err:#})",
+                            file.display()
+                        ))
+                    })
+                    .ok()
+            } else {
+                None
+            };
+            let change = sync_path(&store, models, &file, git.as_ref(), opts, &mut report)?;
+            match change {
+                PathChange::Indexed => events.push(json!({
+                    "event": "indexed",
+                    "path": file.to_string_lossy(),
+                    "chunks": report.chunks_added - chunks_before,
+                })),
+                PathChange::Removed => events.push(json!({
+                    "event": "removed",
+                    "path": file.to_string_lossy(),
+                })),
+                PathChange::Unchanged => {}
+            }
+        }
+    }
+    tx.commit()?;
+
+    for event in events {
+        emit(event)?;
+    }
+    for warning in report.warnings {
+        emit(json!({ "event": "warning", "message": warning }))?;
+    }
+    for err in report.errors {
+        emit(json!({ "event": "error", "path": err.path, "message": err.message }))?;
+    }
+    Ok(())
+}
+
+/// Map an absolute event path back onto the watch root spelling used at ingest time.
+fn doc_path(roots: &[WatchRoot], changed: &Path) -> Option<PathBuf> {
+    roots.iter().find_map(|root| {
+        let rel = changed.strip_prefix(&root.canonical).ok()?;
+        if rel.as_os_str().is_empty() {
+            Some(root.display.clone())
+        } else {
+            Some(root.display.join(rel))
+        }
+    })
+}
+
+/// Matches the store file and its `-wal`/`-shm`/`-journal` sidecars, and nothing else that
+/// merely shares the store's name as a prefix.
+fn is_store_file(path: &Path, store_canonical: &Path) -> bool {
+    if path == store_canonical {
+        return true;
+    }
+    ["-wal", "-shm", "-journal"].iter().any(|suffix| {
+        let mut sidecar = store_canonical.as_os_str().to_owned();
+        sidecar.push(suffix);
+        path == Path::new(&sidecar)
+    })
+}
+
+fn emit(mut event: Value) -> Result<()> {
+    if let Value::Object(map) = &mut event {
+        let ts = OffsetDateTime::now_utc().format(&Rfc3339)?;
+        map.insert("ts".to_string(), ts.into());
+    }
+    let mut stdout = std::io::stdout().lock();
+    writeln!(stdout, "{}", serde_json::to_string(&event)?)?;
+    stdout.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn event_paths_map_to_root_spelling() {
+        let roots = vec![WatchRoot {
+            display: PathBuf::from("."),
+            canonical: PathBuf::from("/repo"),
+        }];
+        assert_eq!(
+            doc_path(&roots, Path::new("/repo/docs/a.md")),
+            Some(PathBuf::from("./docs/a.md"))
+        );
+        assert_eq!(doc_path(&roots, Path::new("/elsewhere/a.md")), None);
+    }
+
+    #[test]
+    fn store_files_match_exactly() {
+        let store = Path::new("/repo/recall.db");
+        assert!(is_store_file(store, store));
+        assert!(is_store_file(Path::new("/repo/recall.db-wal"), store));
+        assert!(is_store_file(Path::new("/repo/recall.db-shm"), store));
+        assert!(is_store_file(Path::new("/repo/recall.db-journal"), store));
+        assert!(!is_store_file(Path::new("/repo/recall.db-notes.md"), store));
+        assert!(!is_store_file(Path::new("/repo/recall.dbx"), store));
+        assert!(!is_store_file(Path::new("/other/recall.db"), store));
+    }
+}
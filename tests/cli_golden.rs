@@ -64,6 +64,11 @@ fn normalize_json(mut value: Value) -> Value {
             corpus_obj.insert("bytes".to_string(), json!(0));
         }
     }
+    if let Some(ingest) = value.get_mut("ingest")
+        && let Some(obj) = ingest.as_object_mut()
+    {
+        obj.insert("embed_ms".to_string(), json!(0));
+    }
     strip_mtime(&mut value);
     value
 }
@@ -130,6 +135,22 @@ fn golden_cli_outputs() {
         String::from_utf8_lossy(&output.stderr)
     );
 
+    // add --dry-run (writes nothing; the real add below still sees a new doc)
+    let mut cmd = recall_cmd_with_env(config_root);
+    cmd.args([
+        "add",
+        "docs",
+        "--glob",
+        "**/*.txt",
+        "--tag",
+        "docs",
+        "--dry-run",
+        "--json",
+    ]);
+    let dry_run_json = run_json(&mut cmd, root);
+    assert_schema(&schema, &dry_run_json);
+    insta::assert_json_snapshot!("add_dry_run", normalize_json(dry_run_json));
+
     // add
     let mut cmd = recall_cmd_with_env(config_root);
     cmd.args([
@@ -389,3 +410,67 @@ fn sqlite_vec_search() {
         .expect("results array");
     assert!(!results.is_empty(), "expected sqlite-vec results");
 }
+
+#[test]
+fn add_stdin_and_text_under_virtual_path() {
+    let schema = load_schema();
+    let config_temp = TempDir::new().expect("config tempdir");
+    let config_root = config_temp.path();
+    let temp = TempDir::new().expect("tempdir");
+    let root = temp.path();
+
+    let mut cmd = recall_cmd_with_env(config_root);
+    cmd.args(["init", "."]);
+    assert!(cmd.current_dir(root).output().unwrap().status.success());
+
+    let mut cmd = recall_cmd_with_env(config_root);
+    cmd.args(["add", "--stdin", "--as", "notes/a.md", "--json"])
+        .write_stdin("first draft of the deploy notes\n");
+    let add_json = run_json(&mut cmd, root);
+    assert_schema(&schema, &add_json);
+    assert_eq!(add_json["ingest"]["new"], 1);
+
+    let mut cmd = recall_cmd_with_env(config_root);
+    cmd.args([
+        "add",
+        "--text",
+        "second draft of the deploy notes",
+        "--as",
+        "notes/a.md",
+        "--json",
+    ]);
+    let add_json = run_json(&mut cmd, root);
+    assert_schema(&schema, &add_json);
+    assert_eq!(add_json["ingest"]["new"], 0);
+    assert_eq!(add_json["ingest"]["updated"], 1);
+
+    let mut cmd = recall_cmd_with_env(config_root);
+    cmd.args([
+        "query",
+        "--rql",
+        "FROM chunk FILTER doc.path = 'notes/a.md' SELECT doc.path, chunk.text;",
+        "--json",
+    ]);
+    let query_json = run_json(&mut cmd, root);
+    let results = query_json
+        .get("results")
+        .and_then(|v| v.as_array())
+        .expect("results array");
+    let texts: Vec<&str> = results
+        .iter()
+        .filter_map(|item| item.get("chunk"))
+        .filter_map(|chunk| chunk.get("text"))
+        .filter_map(|text| text.as_str())
+        .collect();
+    assert_eq!(texts, vec!["second draft of the deploy notes"]);
+
+    let mut cmd = recall_cmd_with_env(config_root);
+    cmd.args(["add", "--stdin"]).write_stdin("no path\n");
+    let output = cmd.current_dir(root).output().unwrap();
+    assert!(!output.status.success(), "--stdin without --as must fail");
+
+    let mut cmd = recall_cmd_with_env(config_root);
+    cmd.args(["add", "--text", "no path", "--as", " "]);
+    let output = cmd.current_dir(root).output().unwrap();
+    assert!(!output.status.success(), "blank --as must fail");
+}